@@ -2,8 +2,6 @@
 
 set -eu
 
-SOURCE_DIR="$(dirname -- ${BASH_SOURCE[0]:-$0})";
-
 log() {
     echo -e "$1" >&2
 }
@@ -48,8 +46,6 @@ if [[ ! -x ${COMMAND} ]]; then
     fi
     rm "${FILE_NAME}"
 fi
-log "jq: $(jq --version)"
-
 ARGS="${TARGET}"
 
 # Ignore implicit configuration files
@@ -68,5 +64,4 @@ if [[ -n "${INPUT_CONFIG:-}" ]]; then
 fi
 
 log "$ ${COMMAND} ${ARGS}"
-${COMMAND} ${ARGS} --format json | ${SOURCE_DIR}/format_gh.sh || true
-${COMMAND} ${ARGS}
+${COMMAND} ${ARGS} --format github
@@ -0,0 +1,237 @@
+//! Read the `.gitattributes` that mark files to skip
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Look up `linguist-generated`, `linguist-vendored`, and `typos` in `.gitattributes`
+///
+/// Like `git`, `.gitattributes` files are read from the directories between the file and the
+/// root of its repository, with deeper files and later lines taking precedence.
+/// Each directory is only read once.
+#[derive(Debug, Default)]
+pub(crate) struct GitAttributes {
+    dirs: Mutex<HashMap<PathBuf, Arc<DirAttributes>>>,
+}
+
+impl GitAttributes {
+    /// Why `path` should be skipped, if its attributes mark it as generated, vendored, or
+    /// `typos=false`
+    pub(crate) fn skip_reason(&self, path: &Path) -> Option<&'static str> {
+        let mut dirs = Vec::new();
+        for dir in path.ancestors().skip(1) {
+            let attributes = self.get_dir(dir);
+            let is_repo_root = attributes.is_repo_root;
+            dirs.push(attributes);
+            if is_repo_root {
+                break;
+            }
+        }
+
+        let mut state = State::default();
+        for dir in dirs.iter().rev() {
+            for line in dir.lines.iter() {
+                if line.pattern.matched(path, false).is_ignore() {
+                    for (attribute, value) in line.attributes.iter() {
+                        state[*attribute as usize] = *value;
+                    }
+                }
+            }
+        }
+
+        Attribute::ALL
+            .iter()
+            .find(|attribute| state[**attribute as usize] == Some(true))
+            .map(|attribute| attribute.reason())
+    }
+
+    fn get_dir(&self, dir: &Path) -> Arc<DirAttributes> {
+        if let Some(attributes) = self.dirs.lock().unwrap().get(dir) {
+            return attributes.clone();
+        }
+        let attributes = Arc::new(DirAttributes::from_dir(dir));
+        self.dirs
+            .lock()
+            .unwrap()
+            .insert(dir.to_owned(), attributes.clone());
+        attributes
+    }
+}
+
+/// Whether each [`Attribute`] marks the file to be skipped, `None` when unspecified
+type State = [Option<bool>; Attribute::ALL.len()];
+
+#[derive(Debug)]
+struct DirAttributes {
+    lines: Vec<Line>,
+    is_repo_root: bool,
+}
+
+impl DirAttributes {
+    fn from_dir(dir: &Path) -> Self {
+        let path = dir.join(".gitattributes");
+        let lines = match std::fs::read_to_string(&path) {
+            Ok(content) => parse(dir, &content),
+            Err(err) => {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    log::debug!("{}: could not read: {err}", path.display());
+                }
+                Vec::new()
+            }
+        };
+        Self {
+            lines,
+            is_repo_root: dir.join(".git").exists(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Line {
+    pattern: ignore::gitignore::Gitignore,
+    attributes: Vec<(Attribute, Option<bool>)>,
+}
+
+fn parse(dir: &Path, content: &str) -> Vec<Line> {
+    let mut lines = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        // Quoted patterns and macros aren't supported
+        if line.is_empty() || line.starts_with(['#', '"']) || line.starts_with("[attr]") {
+            continue;
+        }
+        let mut tokens = line.split_ascii_whitespace();
+        let Some(pattern) = tokens.next() else {
+            continue;
+        };
+        let attributes: Vec<_> = tokens.filter_map(Attribute::parse).collect();
+        if attributes.is_empty() {
+            continue;
+        }
+
+        let mut builder = ignore::gitignore::GitignoreBuilder::new(dir);
+        let pattern = builder
+            .add_line(None, pattern)
+            .map_err(|err| err.to_string())
+            .and_then(|builder| builder.build().map_err(|err| err.to_string()));
+        match pattern {
+            Ok(pattern) => lines.push(Line {
+                pattern,
+                attributes,
+            }),
+            Err(err) => {
+                log::debug!(
+                    "{}: invalid pattern: {err}",
+                    dir.join(".gitattributes").display()
+                );
+            }
+        }
+    }
+    lines
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Attribute {
+    Typos,
+    Generated,
+    Vendored,
+}
+
+impl Attribute {
+    /// In the order their reasons are preferred
+    const ALL: [Self; 3] = [Self::Typos, Self::Generated, Self::Vendored];
+
+    /// Parse `name`, `-name`, `!name`, or `name=value` into whether it marks the file to be
+    /// skipped
+    fn parse(token: &str) -> Option<(Self, Option<bool>)> {
+        let (name, value) = if let Some(name) = token.strip_prefix('-') {
+            (name, Some(false))
+        } else if let Some(name) = token.strip_prefix('!') {
+            (name, None)
+        } else if let Some((name, value)) = token.split_once('=') {
+            (name, Some(value != "false"))
+        } else {
+            (token, Some(true))
+        };
+        match name {
+            // `typos` being set means the file is checked
+            "typos" => Some((Self::Typos, value.map(|checked| !checked))),
+            "linguist-generated" => Some((Self::Generated, value)),
+            "linguist-vendored" => Some((Self::Vendored, value)),
+            _ => None,
+        }
+    }
+
+    fn reason(self) -> &'static str {
+        match self {
+            Self::Typos => "marked `typos=false` in `.gitattributes`",
+            Self::Generated => "marked `linguist-generated` in `.gitattributes`",
+            Self::Vendored => "marked `linguist-vendored` in `.gitattributes`",
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn skip_reason(content: &str, path: &str) -> Option<&'static str> {
+        let dir = Path::new("/repo");
+        let attributes = GitAttributes::default();
+        attributes.dirs.lock().unwrap().insert(
+            dir.to_owned(),
+            Arc::new(DirAttributes {
+                lines: parse(dir, content),
+                is_repo_root: true,
+            }),
+        );
+        attributes.dirs.lock().unwrap().insert(
+            dir.join("src"),
+            Arc::new(DirAttributes {
+                lines: Vec::new(),
+                is_repo_root: false,
+            }),
+        );
+        attributes.skip_reason(&dir.join(path))
+    }
+
+    #[test]
+    fn test_skip_reason() {
+        let content = "
+# Comment
+*.pb.go linguist-generated
+vendor/** linguist-vendored=true
+src/*.txt typos=false text
+src/keep.txt !typos
+*.md -linguist-generated
+";
+        assert_eq!(
+            skip_reason(content, "src/api.pb.go"),
+            Some("marked `linguist-generated` in `.gitattributes`")
+        );
+        assert_eq!(
+            skip_reason(content, "vendor/lib/mod.rs"),
+            Some("marked `linguist-vendored` in `.gitattributes`")
+        );
+        assert_eq!(
+            skip_reason(content, "src/notes.txt"),
+            Some("marked `typos=false` in `.gitattributes`")
+        );
+        assert_eq!(skip_reason(content, "src/keep.txt"), None);
+        assert_eq!(skip_reason(content, "src/lib.rs"), None);
+        assert_eq!(skip_reason(content, "vendor.rs"), None);
+    }
+
+    #[test]
+    fn test_later_lines_take_precedence() {
+        let content = "
+*.js linguist-generated
+src/*.js linguist-generated=false
+";
+        assert_eq!(
+            skip_reason(content, "dist/app.js"),
+            Some("marked `linguist-generated` in `.gitattributes`")
+        );
+        assert_eq!(skip_reason(content, "src/app.js"), None);
+    }
+}
@@ -2,6 +2,7 @@ use clap::Parser;
 use clap::builder::TypedValueParser;
 
 use typos_cli::config;
+use typos_cli::report;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum, Default)]
 pub(crate) enum Format {
@@ -11,18 +12,34 @@ pub(crate) enum Format {
     Long,
     Json,
     Sarif,
+    Gitlab,
+    Checkstyle,
+    Junit,
+    Github,
 }
 
 impl Format {
-    pub(crate) fn reporter(self) -> Box<dyn typos_cli::report::Report> {
+    pub(crate) fn reporter(self, column_unit: report::ColumnUnit) -> Box<dyn report::Report> {
         match self {
             Format::Silent => Box::new(crate::report::PrintSilent),
             Format::Brief => Box::new(crate::report::PrintBrief),
             Format::Long => Box::new(crate::report::PrintLong),
-            Format::Json => Box::new(crate::report::PrintJson),
-            Format::Sarif => Box::new(crate::report::PrintSarif::default()),
+            Format::Json => Box::new(crate::report::PrintJson { column_unit }),
+            Format::Sarif => Box::new(crate::report::PrintSarif::new(column_unit)),
+            Format::Gitlab => Box::new(crate::report::PrintGitlab::default()),
+            Format::Checkstyle => Box::new(crate::report::PrintCheckstyle::new(column_unit)),
+            Format::Junit => Box::new(crate::report::PrintJunit::new(column_unit)),
+            Format::Github => Box::new(crate::report::PrintGithub::new(column_unit)),
         }
     }
+
+    /// Whether each message is printed as it is found, rather than at the end
+    pub(crate) fn is_streaming(self) -> bool {
+        matches!(
+            self,
+            Format::Silent | Format::Brief | Format::Long | Format::Json
+        )
+    }
 }
 
 #[derive(Debug, Parser)]
@@ -51,6 +68,30 @@ pub(crate) struct Args {
     #[arg(long, help_heading = None)]
     pub(crate) force_exclude: bool,
 
+    /// Only check files and lines changed since the git revision
+    #[arg(long, value_name = "REV", help_heading = None)]
+    pub(crate) diff_base: Option<String>,
+
+    /// Reuse results for files unchanged since a previous run, stored in the directory
+    #[arg(long, value_name = "DIR", conflicts_with = "mode", help_heading = None)]
+    pub(crate) cache_dir: Option<std::path::PathBuf>,
+
+    /// Keep running, re-checking files as they change
+    #[arg(
+        long,
+        conflicts_with_all = ["interactive", "write_baseline", "init", "dump_config", "type_list", "lsp"],
+        help_heading = None
+    )]
+    pub(crate) watch: bool,
+
+    /// Summarize files checked and typos found, like the most frequent typos
+    #[arg(
+        long,
+        conflicts_with_all = ["files", "file_types", "highlight_identifiers", "identifiers", "highlight_words", "words", "dump_config", "type_list", "lsp", "watch"],
+        help_heading = None
+    )]
+    pub(crate) stats: bool,
+
     /// Custom config file
     #[arg(short = 'c', long = "config", help_heading = "Config")]
     pub(crate) custom_config: Option<std::path::PathBuf>,
@@ -59,6 +100,14 @@ pub(crate) struct Args {
     #[arg(long, help_heading = "Config")]
     pub(crate) isolated: bool,
 
+    /// Ignore typos recorded with `--write-baseline`
+    #[arg(long, value_name = "PATH", help_heading = "Config")]
+    pub(crate) baseline: Option<std::path::PathBuf>,
+
+    /// Fail when `--baseline` has entries that no longer match a typo
+    #[arg(long, requires = "baseline", help_heading = "Config")]
+    pub(crate) strict_baseline: bool,
+
     #[command(flatten, next_help_heading = "Config")]
     pub(crate) config: ConfigArgs,
 
@@ -70,6 +119,10 @@ pub(crate) struct Args {
     #[arg(long, short = 'w', group = "mode", help_heading = "Mode")]
     pub(crate) write_changes: bool,
 
+    /// Prompt for how to fix each typo
+    #[arg(long, short = 'i', group = "mode", help_heading = "Mode")]
+    pub(crate) interactive: bool,
+
     /// Debug: Print each file that would be spellchecked.
     #[arg(long, group = "mode", help_heading = "Mode")]
     pub(crate) files: bool,
@@ -94,6 +147,26 @@ pub(crate) struct Args {
     #[arg(long, group = "mode", help_heading = "Mode")]
     pub(crate) words: bool,
 
+    /// Record all current typos to file, for use with `--baseline`
+    #[arg(
+        long,
+        value_name = "PATH",
+        group = "mode",
+        conflicts_with = "baseline",
+        help_heading = "Mode"
+    )]
+    pub(crate) write_baseline: Option<std::path::PathBuf>,
+
+    /// Write a config to file (or add to `Cargo.toml`/`pyproject.toml`) accepting current typos
+    #[arg(
+        long,
+        value_name = "PATH",
+        group = "mode",
+        conflicts_with = "baseline",
+        help_heading = "Mode"
+    )]
+    pub(crate) init: Option<std::path::PathBuf>,
+
     /// Write the current configuration to file with `-` for stdout
     #[arg(long, group = "mode", help_heading = "Mode")]
     pub(crate) dump_config: Option<std::path::PathBuf>,
@@ -102,6 +175,10 @@ pub(crate) struct Args {
     #[arg(long, group = "mode", help_heading = "Mode")]
     pub(crate) type_list: bool,
 
+    /// Run as a Language Server over stdio, for editor integration
+    #[arg(long, group = "mode", help_heading = "Mode")]
+    pub(crate) lsp: bool,
+
     /// Render style for messages
     #[arg(
         long,
@@ -112,6 +189,16 @@ pub(crate) struct Args {
     )]
     pub(crate) format: Format,
 
+    /// How to count columns in reports (SARIF doesn't support `byte`)
+    #[arg(
+        long,
+        default_value_t,
+        help_heading = "Output",
+        value_parser = clap::builder::PossibleValuesParser::new(report::ColumnUnit::variants())
+            .map(|u| u.parse::<report::ColumnUnit>().unwrap())
+    )]
+    pub(crate) column_unit: report::ColumnUnit,
+
     #[command(flatten, next_help_heading = "Output")]
     pub(crate) color: colorchoice_clap::Color,
 
@@ -161,6 +248,12 @@ impl FileArgs {
             binary: self.binary(),
             check_filename: self.check_filename(),
             check_file: self.check_file(),
+            check: None,
+            markup: None,
+            code_blocks: None,
+            skip_generated: None,
+            max_file_size: None,
+            max_line_length: None,
             tokenizer: config::TokenizerConfig {
                 unicode: self.unicode(),
                 ..Default::default()
@@ -0,0 +1,293 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use typos_cli::report::{Context, FileContext, Message, PathContext, Report, Typo};
+
+/// Previously accepted typos, matched by [`Typo::fingerprint`]
+///
+/// Paths are relative to the directory containing the baseline, so it matches regardless of the
+/// current directory or the paths passed on the command line.
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Baseline {
+    pub(crate) typos: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Entry {
+    pub(crate) path: String,
+    pub(crate) typo: String,
+    pub(crate) corrections: Vec<String>,
+    pub(crate) fingerprint: String,
+}
+
+impl Entry {
+    /// Record `typo`, with its path made relative to `root`
+    fn from_typo(typo: &Typo<'_>, root: &Path) -> Self {
+        let path = match &typo.context {
+            Some(Context::File(context)) => relative_path(context.path, root),
+            Some(Context::Path(context)) => relative_path(context.path, root),
+            _ => PathBuf::new(),
+        };
+        let context = match &typo.context {
+            Some(Context::File(context)) => Some(
+                FileContext::default()
+                    .path(&path)
+                    .line_num(context.line_num)
+                    .into(),
+            ),
+            Some(Context::Path(_)) => Some(PathContext::default().path(&path).into()),
+            _ => None,
+        };
+        let fingerprint = typo.clone().context(context).fingerprint();
+        let corrections = match &typo.corrections {
+            typos::Status::Corrections(corrections) => {
+                corrections.iter().map(|c| c.clone().into_owned()).collect()
+            }
+            typos::Status::Valid | typos::Status::Invalid => Vec::new(),
+        };
+        Self {
+            path: path
+                .display()
+                .to_string()
+                .replace(std::path::MAIN_SEPARATOR, "/"),
+            typo: typo.typo.to_owned(),
+            corrections,
+            fingerprint,
+        }
+    }
+
+    /// Whether this entry is for `path`, or a file under it
+    fn is_under(&self, root: &Path, path: &Path) -> bool {
+        root.join(&self.path).starts_with(path)
+    }
+
+    fn key(&self) -> (String, String, String) {
+        (
+            self.path.clone(),
+            self.typo.clone(),
+            self.fingerprint.clone(),
+        )
+    }
+}
+
+impl Baseline {
+    pub(crate) fn from_file(path: &Path) -> Result<Self, anyhow::Error> {
+        let s = std::fs::read_to_string(path).map_err(|err| {
+            let kind = err.kind();
+            std::io::Error::new(
+                kind,
+                format!("could not read baseline at `{}`", path.display()),
+            )
+        })?;
+        let baseline = serde_json::from_str(&s)
+            .map_err(|err| anyhow::format_err!("invalid baseline `{}`: {err}", path.display()))?;
+        Ok(baseline)
+    }
+
+    pub(crate) fn to_file(&self, path: &Path) -> Result<(), std::io::Error> {
+        let mut output = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        output.push('\n');
+        std::fs::write(path, output)
+    }
+}
+
+/// `path` as found while checking, relative to `root` when under it
+///
+/// `root` is expected to be [`absolute`].
+fn relative_path(path: &Path, root: &Path) -> PathBuf {
+    if path == Path::new("-") {
+        return path.to_owned();
+    }
+    let path = absolute(path);
+    match path.strip_prefix(root) {
+        Ok(relative) => relative.to_owned(),
+        Err(_) => path,
+    }
+}
+
+/// `path` made absolute, resolving symlinks when it exists
+pub(crate) fn absolute(path: &Path) -> PathBuf {
+    if let Ok(path) = path.canonicalize() {
+        return path;
+    }
+    let path = std::path::absolute(path).unwrap_or_else(|_| path.to_owned());
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// The directory that paths in the baseline at `path` are relative to
+pub(crate) fn root(path: &Path) -> PathBuf {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    absolute(if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    })
+}
+
+/// Tracks which [`Baseline`] entries have been seen during a run
+#[derive(Debug)]
+pub(crate) struct BaselineFilter {
+    root: PathBuf,
+    remaining: Mutex<BTreeMap<(String, String, String), Vec<Entry>>>,
+}
+
+impl BaselineFilter {
+    /// Match typos against `baseline`, whose paths are relative to `root`
+    pub(crate) fn new(baseline: Baseline, root: PathBuf) -> Self {
+        let mut remaining: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for entry in baseline.typos {
+            remaining.entry(entry.key()).or_default().push(entry);
+        }
+        Self {
+            root,
+            remaining: Mutex::new(remaining),
+        }
+    }
+
+    /// Whether `msg` is accepted by the baseline
+    ///
+    /// Each baseline entry can only accept one typo.
+    pub(crate) fn matches(&self, msg: &Message<'_>) -> bool {
+        let Message::Typo(typo) = msg else {
+            return false;
+        };
+        if typo.corrections.is_valid() {
+            return false;
+        }
+        let key = Entry::from_typo(typo, &self.root).key();
+        let mut remaining = self.remaining.lock().unwrap();
+        let Some(entries) = remaining.get_mut(&key) else {
+            return false;
+        };
+        entries.pop();
+        if entries.is_empty() {
+            remaining.remove(&key);
+        }
+        true
+    }
+
+    /// Baseline entries under `checked` paths that no longer match a typo
+    ///
+    /// Entries for other paths weren't looked for, like when only some files are checked.
+    pub(crate) fn unused(&self, checked: &[PathBuf]) -> Vec<Entry> {
+        let checked: Vec<_> = checked.iter().map(|path| absolute(path)).collect();
+        self.remaining
+            .lock()
+            .unwrap()
+            .values()
+            .flatten()
+            .filter(|entry| checked.iter().any(|path| entry.is_under(&self.root, path)))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Record typos for [`Baseline::to_file`], rather than reporting them
+pub(crate) struct WriteBaseline<'r> {
+    root: PathBuf,
+    typos: Mutex<Vec<Entry>>,
+    reporter: &'r dyn Report,
+}
+
+impl<'r> WriteBaseline<'r> {
+    /// Record typos with their paths relative to `root`
+    pub(crate) fn new(root: PathBuf, reporter: &'r dyn Report) -> Self {
+        Self {
+            root,
+            typos: Default::default(),
+            reporter,
+        }
+    }
+
+    pub(crate) fn into_baseline(self) -> Baseline {
+        let mut typos = self.typos.into_inner().unwrap();
+        typos.sort_unstable();
+        Baseline { typos }
+    }
+}
+
+impl Report for WriteBaseline<'_> {
+    fn report(&self, msg: Message<'_>) -> Result<(), std::io::Error> {
+        match &msg {
+            Message::Typo(typo) if !typo.corrections.is_valid() => {
+                self.typos
+                    .lock()
+                    .unwrap()
+                    .push(Entry::from_typo(typo, &self.root));
+                Ok(())
+            }
+            _ => self.reporter.report(msg),
+        }
+    }
+
+    fn generate_final_result(&self) -> Result<(), std::io::Error> {
+        self.reporter.generate_final_result()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn typo<'m>(path: &'m Path, line_num: usize, line: &'m str) -> Message<'m> {
+        Typo::default()
+            .context(Some(
+                FileContext::default().path(path).line_num(line_num).into(),
+            ))
+            .buffer(std::borrow::Cow::Borrowed(line.as_bytes()))
+            .typo("teh")
+            .corrections(typos::Status::Corrections(vec!["the".into()]))
+            .into()
+    }
+
+    #[test]
+    fn matches_moved_line() {
+        let root = absolute(Path::new("."));
+        let path = Path::new("./file.txt");
+        let recorder = WriteBaseline::new(root.clone(), &crate::report::PrintSilent);
+        recorder.report(typo(path, 1, "teh  cat")).unwrap();
+        let filter = BaselineFilter::new(recorder.into_baseline(), root.clone());
+
+        assert!(filter.matches(&typo(path, 10, "  teh cat")));
+        assert!(filter.unused(&[PathBuf::from(".")]).is_empty());
+    }
+
+    #[test]
+    fn matches_once() {
+        let root = absolute(Path::new("."));
+        let path = Path::new("./file.txt");
+        let recorder = WriteBaseline::new(root.clone(), &crate::report::PrintSilent);
+        recorder.report(typo(path, 1, "teh cat")).unwrap();
+        let filter = BaselineFilter::new(recorder.into_baseline(), root.clone());
+
+        assert!(filter.matches(&typo(path, 1, "teh cat")));
+        assert!(!filter.matches(&typo(path, 2, "teh cat")));
+    }
+
+    #[test]
+    fn reports_unused() {
+        let root = absolute(Path::new("."));
+        let path = Path::new("./file.txt");
+        let recorder = WriteBaseline::new(root.clone(), &crate::report::PrintSilent);
+        recorder.report(typo(path, 1, "teh cat")).unwrap();
+        let filter = BaselineFilter::new(recorder.into_baseline(), root.clone());
+
+        assert!(!filter.matches(&typo(path, 1, "teh dog")));
+        let unused = filter.unused(&[PathBuf::from(".")]);
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].typo, "teh");
+    }
+}
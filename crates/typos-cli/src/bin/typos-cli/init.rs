@@ -0,0 +1,470 @@
+//! Bootstrap a config from the typos currently in a project
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use crate::baseline::Entry;
+
+/// The config file to create for projects without one
+pub(crate) const NEW_CONFIG_FILE_NAME: &str = "_typos.toml";
+
+/// Files with this many typos are likely generated or data, rather than something to fix
+const EXCLUDE_THRESHOLD: usize = 10;
+
+/// Config changes to accept the typos found in a project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Proposals {
+    excludes: Vec<Exclude>,
+    tokens: Vec<Token>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Exclude {
+    path: String,
+    typos: usize,
+    accept: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    token: String,
+    identifier: bool,
+    /// Only found in this file type
+    file_type: Option<String>,
+    occurrences: usize,
+    files: Vec<String>,
+    corrections: Vec<String>,
+    accept: bool,
+}
+
+impl Proposals {
+    /// Group `typos` by file and token
+    ///
+    /// Tokens that recur are likely project terminology and accepted by default,
+    /// while one-offs are likely real typos.
+    pub(crate) fn new(typos: &[Entry], file_type: impl Fn(&str) -> Option<String>) -> Self {
+        let typos: Vec<_> = typos
+            .iter()
+            .filter(|entry| !entry.corrections.is_empty())
+            .collect();
+
+        let mut by_path: BTreeMap<&str, usize> = BTreeMap::new();
+        for entry in typos.iter() {
+            *by_path.entry(entry.path.as_str()).or_default() += 1;
+        }
+        let excludes = by_path
+            .iter()
+            .filter(|(_, count)| EXCLUDE_THRESHOLD <= **count)
+            .map(|(path, count)| Exclude {
+                path: path.trim_start_matches("./").to_owned(),
+                typos: *count,
+                accept: true,
+            })
+            .collect();
+
+        let file_types: BTreeMap<&str, Option<String>> = by_path
+            .keys()
+            .map(|path| (*path, file_type(path)))
+            .collect();
+        let multiple_types = file_types.values().collect::<BTreeSet<_>>().len() > 1;
+
+        let mut by_token: BTreeMap<String, Vec<&Entry>> = BTreeMap::new();
+        for entry in typos.iter() {
+            if EXCLUDE_THRESHOLD <= by_path[entry.path.as_str()] {
+                continue;
+            }
+            let key = if is_identifier(&entry.typo) {
+                entry.typo.clone()
+            } else {
+                entry.typo.to_lowercase()
+            };
+            by_token.entry(key).or_default().push(entry);
+        }
+        let tokens = by_token
+            .into_iter()
+            .map(|(token, entries)| {
+                let files: BTreeSet<_> = entries.iter().map(|e| e.path.clone()).collect();
+                let types: BTreeSet<_> = entries
+                    .iter()
+                    .map(|e| &file_types[e.path.as_str()])
+                    .collect();
+                let file_type = if multiple_types && types.len() == 1 {
+                    types.into_iter().next().cloned().flatten()
+                } else {
+                    None
+                };
+                let occurrences = entries.len();
+                Token {
+                    identifier: is_identifier(&token),
+                    token,
+                    file_type,
+                    occurrences,
+                    accept: 2 <= files.len() || 3 <= occurrences,
+                    files: files.into_iter().collect(),
+                    corrections: entries[0].corrections.clone(),
+                }
+            })
+            .collect();
+
+        Self { excludes, tokens }
+    }
+
+    /// Let the user decide on each proposal, with the automatic choice as the default
+    pub(crate) fn prompt(
+        &mut self,
+        input: &mut dyn BufRead,
+        output: &mut dyn Write,
+    ) -> Result<(), std::io::Error> {
+        for exclude in self.excludes.iter_mut() {
+            let question = format!("Exclude `{}` with {} typos?", exclude.path, exclude.typos);
+            ask(input, output, &question, &mut exclude.accept)?;
+        }
+        for token in self.tokens.iter_mut() {
+            let question = format!(
+                "Accept `{}` ({}, suggested {})?",
+                token.token,
+                token.usage(),
+                itertools::join(token.corrections.iter().map(|c| format!("`{c}`")), ", ")
+            );
+            ask(input, output, &question, &mut token.accept)?;
+        }
+        Ok(())
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.excludes.iter().any(|e| e.accept) && !self.tokens.iter().any(|t| t.accept)
+    }
+
+    fn to_config(&self) -> typos_cli::config::Config {
+        let mut config = typos_cli::config::Config::default();
+        for exclude in self.excludes.iter().filter(|e| e.accept) {
+            config.files.extend_exclude.push(exclude.path.clone());
+        }
+        for token in self.tokens.iter().filter(|t| t.accept) {
+            let engine = match token.file_type.as_deref() {
+                Some(file_type) => {
+                    &mut config
+                        .type_
+                        .patterns
+                        .entry(kstring::KString::from_ref(file_type))
+                        .or_default()
+                        .engine
+                }
+                None => &mut config.default,
+            };
+            let token = kstring::KString::from_ref(&token.token);
+            if is_identifier(&token) {
+                engine.dict.extend_identifiers.insert(token.clone(), token);
+            } else {
+                engine.dict.extend_words.insert(token.clone(), token);
+            }
+        }
+        config
+    }
+
+    /// Render the accepted proposals as a commented config, nested under `parents`
+    fn render(&self, parents: &[&str]) -> Result<String, anyhow::Error> {
+        let mut table = toml::Table::try_from(self.to_config())?;
+        prune(&mut table);
+        for parent in parents.iter().rev() {
+            let mut nested = toml::Table::new();
+            nested.insert((*parent).to_owned(), toml::Value::Table(table));
+            table = nested;
+        }
+        let rendered = toml::to_string_pretty(&table)?;
+
+        let mut output = String::new();
+        output.push_str("# Generated by `typos --init` from the typos found at the time\n");
+        output.push_str(
+            "# See https://github.com/crate-ci/typos/blob/master/docs/reference.md for more options\n",
+        );
+        for line in rendered.lines() {
+            output.push_str(line);
+            if let Some(comment) = self.comment(line) {
+                output.push_str("  # ");
+                output.push_str(&comment);
+            }
+            output.push('\n');
+        }
+        Ok(output)
+    }
+
+    fn comment(&self, line: &str) -> Option<String> {
+        let (key, _) = line.trim().split_once(" = ")?;
+        if key == "extend-exclude" {
+            return Some(format!("files with {EXCLUDE_THRESHOLD}+ typos"));
+        }
+        let key = key.trim_matches('"');
+        self.tokens
+            .iter()
+            .find(|t| t.accept && t.token == key)
+            .map(|t| t.usage())
+    }
+
+    /// Write the accepted proposals to a new `_typos.toml`-like file or to an existing
+    /// `Cargo.toml` or `pyproject.toml`
+    pub(crate) fn write(&self, path: &Path) -> Result<bool, anyhow::Error> {
+        if self.is_empty() {
+            return Ok(false);
+        }
+
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            // `Cargo.toml` is only added to, as a new one wouldn't be a valid manifest
+            Err(err)
+                if err.kind() == std::io::ErrorKind::NotFound
+                    && path.file_name() != Some("Cargo.toml".as_ref()) =>
+            {
+                String::new()
+            }
+            Err(err) => anyhow::bail!("could not read `{}`: {err}", path.display()),
+        };
+        let parents = config_parents(path, &content)?;
+
+        if parents.is_empty() {
+            if path.exists() {
+                anyhow::bail!("`{}` already exists", path.display());
+            }
+            std::fs::write(path, self.render(parents)?)?;
+        } else {
+            if path.exists() && typos_cli::config::Config::from_file(path)?.is_some() {
+                anyhow::bail!("`{}` already has a typos config", path.display());
+            }
+            let mut content = content;
+            if !content.is_empty() {
+                if !content.ends_with('\n') {
+                    content.push('\n');
+                }
+                content.push('\n');
+            }
+            content.push_str(&self.render(parents)?);
+            std::fs::write(path, content)?;
+        }
+        Ok(true)
+    }
+
+    /// Report what wasn't accepted, as those are likely typos to fix
+    pub(crate) fn summarize(&self, output: &mut dyn Write) -> Result<(), std::io::Error> {
+        for token in self.tokens.iter().filter(|t| !t.accept) {
+            writeln!(
+                output,
+                "`{}` should be {} ({})",
+                token.token,
+                itertools::join(token.corrections.iter().map(|c| format!("`{c}`")), ", "),
+                itertools::join(token.files.iter(), ", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Token {
+    fn usage(&self) -> String {
+        let times = if self.occurrences == 1 {
+            "once".to_owned()
+        } else {
+            format!("{} times", self.occurrences)
+        };
+        let files = if self.files.len() == 1 {
+            "1 file".to_owned()
+        } else {
+            format!("{} files", self.files.len())
+        };
+        format!("{times} in {files}")
+    }
+}
+
+fn ask(
+    input: &mut dyn BufRead,
+    output: &mut dyn Write,
+    question: &str,
+    choice: &mut bool,
+) -> Result<(), std::io::Error> {
+    let hint = if *choice { "[Y/n]" } else { "[y/N]" };
+    loop {
+        write!(output, "{question} {hint} ")?;
+        output.flush()?;
+        let mut answer = String::new();
+        if input.read_line(&mut answer)? == 0 {
+            return Ok(());
+        }
+        match answer.trim().to_ascii_lowercase().as_str() {
+            "" => return Ok(()),
+            "y" | "yes" => {
+                *choice = true;
+                return Ok(());
+            }
+            "n" | "no" => {
+                *choice = false;
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Whether `token` can only be accepted as a whole identifier, rather than a word
+fn is_identifier(token: &str) -> bool {
+    token.chars().any(|c| !c.is_alphabetic())
+        || token
+            .chars()
+            .zip(token.chars().skip(1))
+            .any(|(a, b)| a.is_lowercase() && b.is_uppercase())
+}
+
+/// The nearest config file for files in `dir`, found like `typos` does
+pub(crate) fn find_config(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors().find_map(|dir| {
+        typos_cli::config::SUPPORTED_FILE_NAMES
+            .iter()
+            .map(|name| dir.join(name))
+            .find(|path| {
+                path.is_file()
+                    && typos_cli::config::Config::from_file(path).is_ok_and(|c| c.is_some())
+            })
+    })
+}
+
+/// The tables a config is nested under in `path`, like `tool.typos` in `pyproject.toml`
+fn config_parents(path: &Path, content: &str) -> Result<&'static [&'static str], anyhow::Error> {
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let parents: &[&str] = match file_name {
+        "Cargo.toml" => {
+            let manifest: toml::Table = toml::from_str(content)?;
+            if manifest.contains_key("package") {
+                &["package", "metadata", "typos"]
+            } else {
+                &["workspace", "metadata", "typos"]
+            }
+        }
+        "pyproject.toml" => &["tool", "typos"],
+        _ => &[],
+    };
+    Ok(parents)
+}
+
+/// The `[default.extend-words]` table in the config at `path`, with its contents `content`
+pub(crate) fn extend_words_table(path: &Path, content: &str) -> String {
+    let parents = config_parents(path, content).unwrap_or_default();
+    let mut table = String::new();
+    for parent in parents {
+        table.push_str(parent);
+        table.push('.');
+    }
+    table.push_str("default.extend-words");
+    format!("[{table}]")
+}
+
+/// Remove empty tables and arrays, so only the changes are written
+fn prune(table: &mut toml::Table) {
+    table.retain(|_, value| match value {
+        toml::Value::Table(table) => {
+            prune(table);
+            !table.is_empty()
+        }
+        toml::Value::Array(array) => !array.is_empty(),
+        _ => true,
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(path: &str, typo: &str) -> Entry {
+        Entry {
+            path: path.to_owned(),
+            typo: typo.to_owned(),
+            corrections: vec!["fixed".to_owned()],
+            fingerprint: String::new(),
+        }
+    }
+
+    fn file_type(path: &str) -> Option<String> {
+        Path::new(path)
+            .extension()
+            .map(|ext| ext.to_string_lossy().into_owned())
+    }
+
+    #[test]
+    fn groups_tokens() {
+        let mut typos = vec![
+            entry("./a.md", "projectterm"),
+            entry("./b.md", "Projectterm"),
+            entry("./a.md", "teh"),
+            entry("./lib.rs", "fooBarr"),
+            entry("./lib.rs", "fooBarr"),
+            entry("./lib.rs", "fooBarr"),
+        ];
+        typos.extend((0..EXCLUDE_THRESHOLD).map(|_| entry("./data.csv", "xyz")));
+        let proposals = Proposals::new(&typos, file_type);
+
+        let rendered = proposals.render(&[]).unwrap();
+        let expected = "\
+# Generated by `typos --init` from the typos found at the time
+# See https://github.com/crate-ci/typos/blob/master/docs/reference.md for more options
+[files]
+extend-exclude = [\"data.csv\"]  # files with 10+ typos
+
+[type.md.extend-words]
+projectterm = \"projectterm\"  # 2 times in 2 files
+
+[type.rs.extend-identifiers]
+fooBarr = \"fooBarr\"  # 3 times in 1 file
+";
+        assert_eq!(rendered, expected);
+
+        let mut summary = Vec::new();
+        proposals.summarize(&mut summary).unwrap();
+        assert_eq!(
+            String::from_utf8(summary).unwrap(),
+            "`teh` should be `fixed` (./a.md)\n"
+        );
+    }
+
+    #[test]
+    fn prompt_overrides_defaults() {
+        let typos = vec![entry("./a.md", "teh"), entry("./b.md", "teh")];
+        let mut proposals = Proposals::new(&typos, |_| None);
+        let mut input = std::io::Cursor::new("maybe\nn\n");
+        let mut output = Vec::new();
+        proposals.prompt(&mut input, &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Accept `teh` (2 times in 2 files, suggested `fixed`)? [Y/n] \
+Accept `teh` (2 times in 2 files, suggested `fixed`)? [Y/n] "
+        );
+        assert!(proposals.is_empty());
+    }
+
+    #[test]
+    fn extend_words_table_nested() {
+        assert_eq!(
+            extend_words_table(Path::new("_typos.toml"), ""),
+            "[default.extend-words]"
+        );
+        assert_eq!(
+            extend_words_table(Path::new("pyproject.toml"), ""),
+            "[tool.typos.default.extend-words]"
+        );
+        assert_eq!(
+            extend_words_table(Path::new("Cargo.toml"), "[package]\nname = \"foo\"\n"),
+            "[package.metadata.typos.default.extend-words]"
+        );
+    }
+
+    #[test]
+    fn render_nested() {
+        let typos = vec![entry("./a.md", "teh"), entry("./b.md", "teh")];
+        let proposals = Proposals::new(&typos, |_| None);
+        let rendered = proposals.render(&["package", "metadata", "typos"]).unwrap();
+        assert!(
+            rendered.contains("[package.metadata.typos.default.extend-words]\nteh = \"teh\""),
+            "{rendered}"
+        );
+    }
+}
@@ -0,0 +1,230 @@
+//! Prompt for how to handle each typo, for `--interactive`
+
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use typos_cli::file::Fix;
+use typos_cli::report::{Context, Typo};
+
+pub(crate) struct Prompt {
+    state: Mutex<State>,
+}
+
+struct State {
+    input: Box<dyn BufRead + Send>,
+    output: Box<dyn Write + Send>,
+    /// Tokens to leave as-is for the rest of the run
+    ignored: HashSet<String>,
+    quit: bool,
+}
+
+impl Prompt {
+    pub(crate) fn new(input: Box<dyn BufRead + Send>, output: Box<dyn Write + Send>) -> Self {
+        Self {
+            state: Mutex::new(State {
+                input,
+                output,
+                ignored: Default::default(),
+                quit: false,
+            }),
+        }
+    }
+}
+
+impl typos_cli::file::Fixer for Prompt {
+    fn fix(&self, typo: &Typo<'_>) -> Result<Fix, std::io::Error> {
+        let mut state = self.state.lock().unwrap();
+        let typos::Status::Corrections(corrections) = &typo.corrections else {
+            // Disallowed by the config, so there is nothing to choose
+            return Ok(Fix::Skip);
+        };
+        if state.quit {
+            return Ok(Fix::Skip);
+        }
+        if state.ignored.contains(typo.typo) {
+            return Ok(Fix::Ignore);
+        }
+
+        writeln!(state.output, "{}", crate::report::render_typo(typo))?;
+        if 1 < corrections.len() {
+            for (i, correction) in corrections.iter().enumerate() {
+                writeln!(state.output, "  {}) {correction}", i + 1)?;
+            }
+        }
+        let choices = if corrections.len() == 1 {
+            format!("Replace with `{}`? [y]es", corrections[0])
+        } else {
+            format!("Replace with? [1-{}]", corrections.len())
+        };
+        loop {
+            write!(
+                state.output,
+                "{choices}, [s]kip, [i]gnore, [a]dd to config, [q]uit: "
+            )?;
+            state.output.flush()?;
+            let mut answer = String::new();
+            if state.input.read_line(&mut answer)? == 0 {
+                writeln!(state.output)?;
+                state.quit = true;
+                return Ok(Fix::Skip);
+            }
+            let answer = answer.trim();
+            match answer {
+                "" | "y" if corrections.len() == 1 => {
+                    return Ok(Fix::Correct(corrections[0].clone().into_owned()));
+                }
+                "s" => return Ok(Fix::Skip),
+                "i" => {
+                    state.ignored.insert(typo.typo.to_owned());
+                    return Ok(Fix::Ignore);
+                }
+                "a" => {
+                    let config_path = add_to_config(typo)?;
+                    writeln!(
+                        state.output,
+                        "added `{}` to `{}`",
+                        typo.typo,
+                        config_path.display()
+                    )?;
+                    state.ignored.insert(typo.typo.to_owned());
+                    return Ok(Fix::Ignore);
+                }
+                "q" => {
+                    state.quit = true;
+                    return Ok(Fix::Skip);
+                }
+                _ => {
+                    if let Some(correction) = answer
+                        .parse::<usize>()
+                        .ok()
+                        .and_then(|i| i.checked_sub(1))
+                        .and_then(|i| corrections.get(i))
+                    {
+                        return Ok(Fix::Correct(correction.clone().into_owned()));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Mark the typo as valid in the nearest config file, creating one if needed
+fn add_to_config(typo: &Typo<'_>) -> Result<PathBuf, std::io::Error> {
+    let path = match &typo.context {
+        Some(Context::File(context)) => context.path,
+        Some(Context::Path(context)) => context.path,
+        _ => Path::new("-"),
+    };
+    let dir = if path == Path::new("-") {
+        std::env::current_dir()?
+    } else {
+        let path = path.canonicalize()?;
+        path.parent().map(Path::to_owned).unwrap_or(path)
+    };
+    let config_path = crate::init::find_config(&dir)
+        .unwrap_or_else(|| dir.join(crate::init::NEW_CONFIG_FILE_NAME));
+
+    let content = if config_path.exists() {
+        std::fs::read_to_string(&config_path)?
+    } else {
+        String::new()
+    };
+    let table = crate::init::extend_words_table(&config_path, &content);
+    let content = insert_extend_word(&content, &table, &typo.typo.to_lowercase());
+    std::fs::write(&config_path, content)?;
+    Ok(config_path)
+}
+
+/// Add `word` to `table`, like `[default.extend-words]`
+fn insert_extend_word(content: &str, table: &str, word: &str) -> String {
+    let entry = format!("{word} = \"{word}\"\n");
+    let mut lines: Vec<_> = content.split_inclusive('\n').collect();
+    match lines.iter().position(|line| line.trim() == table) {
+        Some(i) => {
+            lines.insert(i + 1, &entry);
+            lines.concat()
+        }
+        None => {
+            let separator = if content.is_empty() {
+                ""
+            } else if content.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            };
+            format!("{content}{separator}{table}\n{entry}")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use typos_cli::file::Fixer as _;
+
+    fn typo<'t>(word: &'t str, corrections: &[&'static str]) -> Typo<'t> {
+        Typo::default()
+            .typo(word)
+            .buffer(std::borrow::Cow::Borrowed(word.as_bytes()))
+            .corrections(typos::Status::Corrections(
+                corrections.iter().map(|c| (*c).into()).collect(),
+            ))
+    }
+
+    fn prompt(input: &'static str) -> Prompt {
+        Prompt::new(
+            Box::new(std::io::Cursor::new(input)),
+            Box::new(std::io::sink()),
+        )
+    }
+
+    #[test]
+    fn choices() {
+        let prompt = prompt("\n2\ns\ni\nq\n");
+        assert_eq!(
+            prompt.fix(&typo("teh", &["the"])).unwrap(),
+            Fix::Correct("the".to_owned())
+        );
+        assert_eq!(
+            prompt.fix(&typo("tets", &["test", "tests"])).unwrap(),
+            Fix::Correct("tests".to_owned())
+        );
+        assert_eq!(prompt.fix(&typo("teh", &["the"])).unwrap(), Fix::Skip);
+        assert_eq!(prompt.fix(&typo("teh", &["the"])).unwrap(), Fix::Ignore);
+        assert_eq!(prompt.fix(&typo("teh", &["the"])).unwrap(), Fix::Ignore);
+        assert_eq!(prompt.fix(&typo("tets", &["test"])).unwrap(), Fix::Skip);
+        assert_eq!(prompt.fix(&typo("tets", &["test"])).unwrap(), Fix::Skip);
+    }
+
+    #[test]
+    fn reprompts_on_invalid_answer() {
+        let prompt = prompt("\n3\nx\n1\n");
+        assert_eq!(
+            prompt.fix(&typo("tets", &["test", "tests"])).unwrap(),
+            Fix::Correct("test".to_owned())
+        );
+    }
+
+    #[test]
+    fn quits_at_end_of_input() {
+        let prompt = prompt("");
+        assert_eq!(prompt.fix(&typo("teh", &["the"])).unwrap(), Fix::Skip);
+    }
+
+    #[test]
+    fn insert_into_existing_table() {
+        let table = "[default.extend-words]";
+        let content = "[default.extend-words]\nfoo = \"foo\"\n";
+        assert_eq!(
+            insert_extend_word(content, table, "teh"),
+            "[default.extend-words]\nteh = \"teh\"\nfoo = \"foo\"\n"
+        );
+        assert_eq!(
+            insert_extend_word("[files]\nextend-exclude = []", table, "teh"),
+            "[files]\nextend-exclude = []\n\n[default.extend-words]\nteh = \"teh\"\n"
+        );
+    }
+}
@@ -0,0 +1,615 @@
+//! Language Server Protocol server over stdio
+//!
+//! Only full-document sync is supported, re-checking the whole document on every change.
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::{Value, json};
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+pub(crate) struct Server {
+    overrides: typos_cli::config::Config,
+    isolated: bool,
+    root: Option<PathBuf>,
+    documents: BTreeMap<String, String>,
+}
+
+impl Server {
+    pub(crate) fn new(overrides: typos_cli::config::Config, isolated: bool) -> Self {
+        Self {
+            overrides,
+            isolated,
+            root: None,
+            documents: Default::default(),
+        }
+    }
+
+    pub(crate) fn run(
+        mut self,
+        reader: &mut dyn BufRead,
+        writer: &mut dyn Write,
+    ) -> Result<(), std::io::Error> {
+        // Configs are loaded once per directory, until one is saved
+        let storage = typos_cli::policy::ConfigStorage::new();
+        let mut engine = typos_cli::policy::ConfigEngine::new(&storage);
+        engine.set_isolated(self.isolated);
+        engine.set_overrides(self.overrides.clone());
+
+        while let Some(msg) = read_message(reader)? {
+            let method = msg.get("method").and_then(Value::as_str);
+            let params = msg.get("params").cloned().unwrap_or(Value::Null);
+            match (method, msg.get("id")) {
+                (Some("exit"), _) => break,
+                (Some(method), Some(id)) => {
+                    let response = match self.handle_request(method, &params) {
+                        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+                        Err((code, message)) => json!({
+                            "jsonrpc": "2.0",
+                            "id": id,
+                            "error": {"code": code, "message": message},
+                        }),
+                    };
+                    write_message(writer, &response)?;
+                }
+                (Some(method), None) => {
+                    for notification in self.handle_notification(&mut engine, method, &params) {
+                        write_message(writer, &notification)?;
+                    }
+                }
+                // Responses, we don't send requests
+                (None, _) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&mut self, method: &str, params: &Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => {
+                let root = params
+                    .pointer("/workspaceFolders/0/uri")
+                    .or_else(|| params.get("rootUri"))
+                    .and_then(Value::as_str)
+                    .and_then(uri_to_path);
+                self.root = root;
+                Ok(json!({
+                    "capabilities": {
+                        "textDocumentSync": {
+                            "openClose": true,
+                            // Full
+                            "change": 1,
+                            "save": {"includeText": false},
+                        },
+                        "codeActionProvider": {"codeActionKinds": ["quickfix"]},
+                    },
+                    "serverInfo": {"name": "typos", "version": env!("CARGO_PKG_VERSION")},
+                }))
+            }
+            "shutdown" => Ok(Value::Null),
+            "textDocument/codeAction" => self.code_actions(params),
+            _ => Err((METHOD_NOT_FOUND, format!("unsupported method `{method}`"))),
+        }
+    }
+
+    fn handle_notification(
+        &mut self,
+        engine: &mut typos_cli::policy::ConfigEngine<'_>,
+        method: &str,
+        params: &Value,
+    ) -> Vec<Value> {
+        let uri = params
+            .pointer("/textDocument/uri")
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned);
+        match (method, uri) {
+            ("textDocument/didOpen", Some(uri)) => {
+                let text = params
+                    .pointer("/textDocument/text")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                self.documents.insert(uri.clone(), text.to_owned());
+                self.publish(engine, &uri)
+            }
+            ("textDocument/didChange", Some(uri)) => {
+                let text = params
+                    .get("contentChanges")
+                    .and_then(Value::as_array)
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change.get("text"))
+                    .and_then(Value::as_str);
+                if let Some(text) = text {
+                    self.documents.insert(uri.clone(), text.to_owned());
+                }
+                self.publish(engine, &uri)
+            }
+            ("textDocument/didClose", Some(uri)) => {
+                self.documents.remove(&uri);
+                vec![publish_diagnostics(&uri, Vec::new())]
+            }
+            ("textDocument/didSave", Some(uri)) => {
+                let is_config = uri_to_path(&uri)
+                    .and_then(|path| path.file_name().map(ToOwned::to_owned))
+                    .is_some_and(|name| {
+                        typos_cli::config::SUPPORTED_FILE_NAMES
+                            .contains(&name.to_str().unwrap_or_default())
+                    });
+                if is_config {
+                    engine.reload();
+                    let uris: Vec<_> = self.documents.keys().cloned().collect();
+                    uris.iter()
+                        .flat_map(|uri| self.publish(engine, uri))
+                        .collect()
+                } else {
+                    Vec::new()
+                }
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn publish(&self, engine: &mut typos_cli::policy::ConfigEngine<'_>, uri: &str) -> Vec<Value> {
+        let Some(text) = self.documents.get(uri) else {
+            return Vec::new();
+        };
+        match diagnostics(engine, uri, text) {
+            Ok(diagnostics) => vec![publish_diagnostics(uri, diagnostics)],
+            Err(err) => {
+                log::error!("{err}");
+                vec![
+                    json!({
+                        "jsonrpc": "2.0",
+                        "method": "window/showMessage",
+                        // Error
+                        "params": {"type": 1, "message": format!("typos: {err}")},
+                    }),
+                    publish_diagnostics(uri, Vec::new()),
+                ]
+            }
+        }
+    }
+
+    fn code_actions(&self, params: &Value) -> Result<Value, (i64, String)> {
+        let uri = params
+            .pointer("/textDocument/uri")
+            .and_then(Value::as_str)
+            .ok_or_else(|| (INVALID_PARAMS, "missing `textDocument.uri`".to_owned()))?;
+        let diagnostics = params
+            .pointer("/context/diagnostics")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        let mut actions = Vec::new();
+        for diagnostic in diagnostics {
+            if diagnostic.get("source").and_then(Value::as_str) != Some("typos") {
+                continue;
+            }
+            let Some(typo) = diagnostic.pointer("/data/typo").and_then(Value::as_str) else {
+                continue;
+            };
+            let corrections: Vec<_> = diagnostic
+                .pointer("/data/corrections")
+                .and_then(Value::as_array)
+                .map(|c| c.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            for correction in corrections.iter() {
+                actions.push(json!({
+                    "title": format!("Replace with `{correction}`"),
+                    "kind": "quickfix",
+                    "diagnostics": [diagnostic],
+                    "isPreferred": corrections.len() == 1,
+                    "edit": {"changes": {uri: [{
+                        "range": diagnostic["range"],
+                        "newText": correction,
+                    }]}},
+                }));
+            }
+            if let Some(edit) =
+                uri_to_path(uri).and_then(|path| self.extend_words_edit(&path, typo))
+            {
+                actions.push(json!({
+                    "title": format!("Add `{typo}` to `extend-words`"),
+                    "kind": "quickfix",
+                    "diagnostics": [diagnostic],
+                    "edit": edit,
+                }));
+            }
+        }
+        Ok(Value::Array(actions))
+    }
+
+    /// Mark `word` as valid in the nearest config file, creating one if needed
+    fn extend_words_edit(&self, path: &Path, word: &str) -> Option<Value> {
+        let word = word.to_lowercase();
+        let entry = format!("{word} = \"{word}\"\n");
+        match path.parent().and_then(crate::init::find_config) {
+            Some(config_path) => {
+                let config_uri = path_to_uri(&config_path);
+                let content = match self.documents.get(&config_uri) {
+                    Some(content) => content.clone(),
+                    None => std::fs::read_to_string(&config_path).ok()?,
+                };
+                let table = crate::init::extend_words_table(&config_path, &content);
+                let (position, new_text) = insert_extend_word(&content, &table, &entry);
+                Some(json!({"changes": {config_uri: [{
+                    "range": {"start": position, "end": position},
+                    "newText": new_text,
+                }]}}))
+            }
+            None => {
+                let dir = self
+                    .root
+                    .as_deref()
+                    .filter(|root| path.starts_with(root))
+                    .or_else(|| path.parent())?;
+                let config_path = dir.join(crate::init::NEW_CONFIG_FILE_NAME);
+                let config_uri = path_to_uri(&config_path);
+                let table = crate::init::extend_words_table(&config_path, "");
+                let (position, new_text) = insert_extend_word("", &table, &entry);
+                Some(json!({"documentChanges": [
+                    {"kind": "create", "uri": config_uri, "options": {"ignoreIfExists": true}},
+                    {
+                        "textDocument": {"uri": config_uri, "version": null},
+                        "edits": [{
+                            "range": {"start": position, "end": position},
+                            "newText": new_text,
+                        }],
+                    },
+                ]}))
+            }
+        }
+    }
+}
+
+fn diagnostics(
+    engine: &mut typos_cli::policy::ConfigEngine<'_>,
+    uri: &str,
+    text: &str,
+) -> Result<Vec<Value>, anyhow::Error> {
+    let Some(path) = uri_to_path(uri) else {
+        // Not a file, like an unsaved buffer, so no config can be found for it
+        return Ok(Vec::new());
+    };
+    if path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| typos_cli::config::SUPPORTED_FILE_NAMES.contains(&n))
+    {
+        // Consistent with walking, config files may have typos
+        return Ok(Vec::new());
+    }
+    let Some(cwd) = path.parent() else {
+        return Ok(Vec::new());
+    };
+
+    engine.init_dir(cwd)?;
+    let policy = engine.policy(&path);
+    if !policy.check_files {
+        return Ok(Vec::new());
+    }
+
+    let mut positions = Positions::new(text);
+    let mut diagnostics = Vec::new();
+    let mut ignores = None;
+    for typo in typos_cli::file::check_bytes(text.as_bytes(), &policy, &mut ignores, false) {
+        let start = positions.position(typo.byte_offset);
+        let end = positions.position(typo.byte_offset + typo.typo.len());
+        let (message, corrections) = match &typo.corrections {
+            typos::Status::Valid => continue,
+            typos::Status::Invalid => (format!("`{}` is disallowed", typo.typo), Vec::new()),
+            typos::Status::Corrections(corrections) => (
+                format!(
+                    "`{}` should be {}",
+                    typo.typo,
+                    itertools::join(corrections.iter().map(|s| format!("`{s}`")), ", ")
+                ),
+                corrections.iter().map(|c| c.as_ref()).collect(),
+            ),
+        };
+        diagnostics.push(json!({
+            "range": {"start": start, "end": end},
+            // Warning
+            "severity": 2,
+            "source": "typos",
+            "message": message,
+            "data": {"typo": typo.typo, "corrections": corrections},
+        }));
+    }
+    Ok(diagnostics)
+}
+
+/// Where and what to insert to add `entry` to `table`, like `[default.extend-words]`
+fn insert_extend_word(content: &str, table: &str, entry: &str) -> (Value, String) {
+    if let Some(line) = content.lines().position(|line| line.trim() == table) {
+        (json!({"line": line + 1, "character": 0}), entry.to_owned())
+    } else {
+        let separator = if content.is_empty() {
+            ""
+        } else if content.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        };
+        let end = Positions::new(content).position(content.len());
+        (end, format!("{separator}{table}\n{entry}"))
+    }
+}
+
+/// Convert byte offsets to LSP's UTF-16 positions
+///
+/// Offsets are expected in increasing order, starting over from the top of `text` otherwise.
+struct Positions<'t> {
+    text: &'t str,
+    offset: usize,
+    line: usize,
+    line_start: usize,
+}
+
+impl<'t> Positions<'t> {
+    fn new(text: &'t str) -> Self {
+        Self {
+            text,
+            offset: 0,
+            line: 0,
+            line_start: 0,
+        }
+    }
+
+    fn position(&mut self, byte_offset: usize) -> Value {
+        if byte_offset < self.offset {
+            self.offset = 0;
+            self.line = 0;
+            self.line_start = 0;
+        }
+        for (i, b) in self.text.as_bytes()[self.offset..byte_offset]
+            .iter()
+            .enumerate()
+        {
+            if *b == b'\n' {
+                self.line += 1;
+                self.line_start = self.offset + i + 1;
+            }
+        }
+        self.offset = byte_offset;
+        let character =
+            typos_cli::report::ColumnUnit::Utf16.count(&self.text[self.line_start..byte_offset]);
+        json!({"line": self.line, "character": character})
+    }
+}
+
+fn publish_diagnostics(uri: &str, diagnostics: Vec<Value>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {"uri": uri, "diagnostics": diagnostics},
+    })
+}
+
+fn read_message(reader: &mut dyn BufRead) -> Result<Option<Value>, std::io::Error> {
+    let mut len = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            let value = value.trim().parse::<usize>().map_err(|err| {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string())
+            })?;
+            len = Some(value);
+        }
+    }
+    let len = len.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "missing `Content-Length` header",
+        )
+    })?;
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body)?;
+    let msg = serde_json::from_slice(&body)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))?;
+    Ok(Some(msg))
+}
+
+fn write_message(writer: &mut dyn Write, msg: &Value) -> Result<(), std::io::Error> {
+    let body = serde_json::to_string(msg).map_err(std::io::Error::other)?;
+    write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    writer.flush()
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    // Skip the authority, like `localhost`
+    let path = &path[path.find('/')?..];
+    let mut decoded = Vec::with_capacity(path.len());
+    let mut bytes = path.bytes();
+    while let Some(b) = bytes.next() {
+        if b == b'%' {
+            let hex = [bytes.next()?, bytes.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            decoded.push(b);
+        }
+    }
+    let path = String::from_utf8(decoded).ok()?;
+    #[cfg(windows)]
+    let path = path.strip_prefix('/').unwrap_or(&path).to_owned();
+    Some(PathBuf::from(path))
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    #[cfg(windows)]
+    let path = format!("/{}", path.replace('\\', "/"));
+    let mut uri = String::from("file://");
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"/-._~:".contains(&b) {
+            uri.push(char::from(b));
+        } else {
+            uri.push_str(&format!("%{b:02X}"));
+        }
+    }
+    uri
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use assert_fs::prelude::*;
+
+    fn script(messages: &[Value]) -> Vec<u8> {
+        let mut input = Vec::new();
+        for msg in messages {
+            write_message(&mut input, msg).unwrap();
+        }
+        input
+    }
+
+    fn responses(output: &[u8]) -> Vec<Value> {
+        let mut reader = std::io::Cursor::new(output);
+        let mut messages = Vec::new();
+        while let Some(msg) = read_message(&mut reader).unwrap() {
+            messages.push(msg);
+        }
+        messages
+    }
+
+    fn run(messages: &[Value]) -> Vec<Value> {
+        let input = script(messages);
+        let mut output = Vec::new();
+        Server::new(Default::default(), false)
+            .run(&mut std::io::Cursor::new(input), &mut output)
+            .unwrap();
+        responses(&output)
+    }
+
+    fn open(uri: &str, text: &str) -> Value {
+        json!({
+            "jsonrpc": "2.0",
+            "method": "textDocument/didOpen",
+            "params": {"textDocument": {"uri": uri, "languageId": "markdown", "version": 1, "text": text}},
+        })
+    }
+
+    #[test]
+    fn initialize() {
+        let responses = run(&[
+            json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"capabilities": {}}}),
+            json!({"jsonrpc": "2.0", "id": 2, "method": "shutdown"}),
+            json!({"jsonrpc": "2.0", "method": "exit"}),
+        ]);
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], 1);
+        assert_eq!(
+            responses[0]["result"]["capabilities"]["textDocumentSync"]["change"],
+            1
+        );
+        assert_eq!(responses[1]["id"], 2);
+        assert_eq!(responses[1]["result"], Value::Null);
+    }
+
+    #[test]
+    fn publish_on_open_and_change() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.child("_typos.toml")
+            .write_str("[default.extend-words]\nhello = \"goodbye\"\n")
+            .unwrap();
+        let uri = path_to_uri(&temp.path().join("file.md"));
+
+        let responses = run(&[
+            open(&uri, "hello\n😀 hello"),
+            json!({
+                "jsonrpc": "2.0",
+                "method": "textDocument/didChange",
+                "params": {
+                    "textDocument": {"uri": uri, "version": 2},
+                    "contentChanges": [{"text": "goodbye"}],
+                },
+            }),
+        ]);
+        assert_eq!(responses.len(), 2);
+        let diagnostics = &responses[0]["params"]["diagnostics"];
+        assert_eq!(diagnostics.as_array().unwrap().len(), 2);
+        assert_eq!(
+            diagnostics[1]["range"],
+            json!({"start": {"line": 1, "character": 3}, "end": {"line": 1, "character": 8}})
+        );
+        assert_eq!(diagnostics[1]["message"], "`hello` should be `goodbye`");
+        assert_eq!(responses[1]["params"]["diagnostics"], json!([]));
+    }
+
+    #[test]
+    fn code_actions() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        temp.child("_typos.toml")
+            .write_str("[default.extend-words]\nhello = \"goodbye\"\n")
+            .unwrap();
+        let uri = path_to_uri(&temp.path().join("file.md"));
+        let config_uri = path_to_uri(&temp.path().join("_typos.toml"));
+
+        let opened = run(&[open(&uri, "hello")]);
+        let diagnostics = opened[0]["params"]["diagnostics"].clone();
+        let responses = run(&[json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/codeAction",
+            "params": {
+                "textDocument": {"uri": uri},
+                "range": diagnostics[0]["range"],
+                "context": {"diagnostics": diagnostics},
+            },
+        })]);
+        let actions = responses[0]["result"].as_array().unwrap();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0]["title"], "Replace with `goodbye`");
+        assert_eq!(actions[0]["edit"]["changes"][&uri][0]["newText"], "goodbye");
+        assert_eq!(actions[1]["title"], "Add `hello` to `extend-words`");
+        assert_eq!(
+            actions[1]["edit"]["changes"][&config_uri][0],
+            json!({
+                "range": {"start": {"line": 1, "character": 0}, "end": {"line": 1, "character": 0}},
+                "newText": "hello = \"hello\"\n",
+            })
+        );
+    }
+
+    #[test]
+    fn insert_into_new_table() {
+        let (position, text) = insert_extend_word(
+            "[files]\nextend-exclude = []",
+            "[default.extend-words]",
+            "a = \"a\"\n",
+        );
+        assert_eq!(position, json!({"line": 1, "character": 19}));
+        assert_eq!(text, "\n\n[default.extend-words]\na = \"a\"\n");
+    }
+
+    #[test]
+    fn positions_out_of_order() {
+        let mut positions = Positions::new("a\nb\nc");
+        assert_eq!(positions.position(4), json!({"line": 2, "character": 0}));
+        assert_eq!(positions.position(2), json!({"line": 1, "character": 0}));
+    }
+
+    #[test]
+    fn uri_round_trip() {
+        let path = std::env::current_dir()
+            .unwrap()
+            .join("a dir")
+            .join("file.md");
+        let uri = path_to_uri(&path);
+        assert!(uri.contains("a%20dir"));
+        assert_eq!(uri_to_path(&uri), Some(path));
+    }
+}
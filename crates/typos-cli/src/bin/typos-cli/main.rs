@@ -1,10 +1,18 @@
-use std::io::{BufRead as _, BufReader, Write as _};
+use std::io::{BufRead as _, BufReader, IsTerminal as _, Write as _};
 use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use clap::Parser;
 
 mod args;
+mod baseline;
+mod init;
+mod interactive;
+mod lsp;
 mod report;
+mod stats;
+mod watch;
 
 use proc_exit::prelude::*;
 
@@ -38,6 +46,8 @@ fn run() -> proc_exit::ExitResult {
         run_dump_config(&args, output_path)
     } else if args.type_list {
         run_type_list(&args)
+    } else if args.lsp {
+        run_lsp(&args)
     } else {
         run_checks(&args)
     }
@@ -45,6 +55,9 @@ fn run() -> proc_exit::ExitResult {
 
 fn run_dump_config(args: &args::Args, output_path: &std::path::Path) -> proc_exit::ExitResult {
     let global_cwd = std::env::current_dir().to_sysexits()?;
+    let display_root = global_cwd
+        .canonicalize()
+        .unwrap_or_else(|_| global_cwd.clone());
 
     let path = &args.path[0];
     let cwd = if path == std::path::Path::new("-") {
@@ -66,10 +79,13 @@ fn run_dump_config(args: &args::Args, output_path: &std::path::Path) -> proc_exi
     let storage = typos_cli::policy::ConfigStorage::new();
     let mut engine = typos_cli::policy::ConfigEngine::new(&storage);
     engine.set_isolated(args.isolated);
+    engine.set_track_provenance(true);
 
     let mut overrides = typos_cli::config::Config::default();
     if let Some(path) = args.custom_config.as_ref() {
-        let custom = typos_cli::config::Config::from_file(path)
+        let custom = typos_cli::config::Loader::new()
+            .track_provenance(true)
+            .load_file(path)
             .with_code(proc_exit::sysexits::CONFIG_ERR)?;
         if let Some(custom) = custom {
             overrides.update(&custom);
@@ -85,6 +101,7 @@ fn run_dump_config(args: &args::Args, output_path: &std::path::Path) -> proc_exi
     let mut defaulted_config = typos_cli::config::Config::from_defaults();
     defaulted_config.update(&config);
     let output = toml::to_string_pretty(&defaulted_config).with_code(proc_exit::Code::FAILURE)?;
+    let output = annotate_provenance(&output, &defaulted_config.provenance, &display_root);
     if output_path == std::path::Path::new("-") {
         std::io::stdout()
             .write_all(output.as_bytes())
@@ -96,6 +113,69 @@ fn run_dump_config(args: &args::Args, output_path: &std::path::Path) -> proc_exi
     Ok(())
 }
 
+/// Comment each value in `output` with the config files it came from
+fn annotate_provenance(
+    output: &str,
+    provenance: &typos_cli::config::Provenance,
+    root: &std::path::Path,
+) -> String {
+    let sources = |key: &[String]| {
+        provenance
+            .get(key)
+            .iter()
+            .map(|path| {
+                path.strip_prefix(root)
+                    .unwrap_or(path)
+                    .display()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut annotated = String::with_capacity(output.len());
+    let mut table = Vec::new();
+    for line in output.lines() {
+        annotated.push_str(line);
+        let mut key = None;
+        if let Some(header) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+            // Arrays of tables are attributed as a whole
+            table = split_toml_key(header);
+            key = Some(table.clone());
+        } else if let Some(header) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            table = split_toml_key(header);
+        } else if let Some((name, _)) = line.split_once(" = ") {
+            if !line.starts_with(char::is_whitespace) {
+                let mut value_key = table.clone();
+                value_key.extend(split_toml_key(name));
+                key = Some(value_key);
+            }
+        }
+        let sources = key.map(|key| sources(&key)).unwrap_or_default();
+        if !sources.is_empty() {
+            annotated.push_str("  # ");
+            annotated.push_str(&sources.join(", "));
+        }
+        annotated.push('\n');
+    }
+    annotated
+}
+
+/// Split a dotted TOML key, like a table header, into its parts
+fn split_toml_key(key: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    let mut quoted = false;
+    for c in key.trim().chars() {
+        match c {
+            '"' | '\'' => quoted = !quoted,
+            '.' if !quoted => parts.push(std::mem::take(&mut part)),
+            c => part.push(c),
+        }
+    }
+    parts.push(part);
+    parts
+}
+
 fn run_type_list(args: &args::Args) -> proc_exit::ExitResult {
     let global_cwd = std::env::current_dir().to_sysexits()?;
 
@@ -145,6 +225,25 @@ fn run_type_list(args: &args::Args) -> proc_exit::ExitResult {
     Ok(())
 }
 
+fn run_lsp(args: &args::Args) -> proc_exit::ExitResult {
+    let mut overrides = typos_cli::config::Config::default();
+    if let Some(path) = args.custom_config.as_ref() {
+        let custom = typos_cli::config::Config::from_file(path)
+            .with_code(proc_exit::sysexits::CONFIG_ERR)?;
+        if let Some(custom) = custom {
+            overrides.update(&custom);
+        }
+    }
+    overrides.update(&args.config.to_config());
+
+    let server = lsp::Server::new(overrides, args.isolated);
+    server
+        .run(&mut std::io::stdin().lock(), &mut std::io::stdout().lock())
+        .with_code(proc_exit::sysexits::IO_ERR)?;
+
+    Ok(())
+}
+
 fn run_checks(args: &args::Args) -> proc_exit::ExitResult {
     let global_cwd = std::env::current_dir()
         .map_err(|err| {
@@ -168,10 +267,20 @@ fn run_checks(args: &args::Args) -> proc_exit::ExitResult {
     overrides.update(&args.config.to_config());
     engine.set_overrides(overrides);
 
+    if let Some(rev) = args.diff_base.as_deref() {
+        let changes = typos_cli::git::Changes::from_rev(&global_cwd, rev)
+            .with_code(proc_exit::sysexits::USAGE_ERR)?;
+        engine.set_changes(Some(changes));
+    }
+
     let mut typos_found = false;
     let mut errors_found = false;
 
-    let file_list = match args.file_list.as_deref() {
+    let file_list: Option<Vec<PathBuf>> = match args.file_list.as_deref() {
+        Some(dash) if dash == "-" && args.interactive => {
+            return Err(proc_exit::sysexits::USAGE_ERR
+                .with_message("Can't read `--file-list` from stdin while using `--interactive`"));
+        }
         Some(dash) if dash == "-" => Some(
             std::io::stdin()
                 .lines()
@@ -189,15 +298,118 @@ fn run_checks(args: &args::Args) -> proc_exit::ExitResult {
         None => None,
     };
 
+    let baseline = args
+        .baseline
+        .as_deref()
+        .map(baseline::Baseline::from_file)
+        .transpose()
+        .with_code(proc_exit::sysexits::CONFIG_ERR)?;
+    let baseline_root = args.baseline.as_deref().map(baseline::root);
+    let baseline_filter = baseline
+        .clone()
+        .zip(baseline_root.clone())
+        .map(|(baseline, root)| baseline::BaselineFilter::new(baseline, root));
+
+    if args.watch && !args.format.is_streaming() {
+        return Err(proc_exit::sysexits::USAGE_ERR.with_message(
+            "`--watch` only supports `--format silent`, `brief`, `long`, or `json`",
+        ));
+    }
+
+    if args.format == args::Format::Sarif && args.column_unit == typos_cli::report::ColumnUnit::Byte
+    {
+        return Err(proc_exit::sysexits::USAGE_ERR.with_message(
+            "SARIF columns can't be counted in bytes, use `--column-unit char` or `utf16`",
+        ));
+    }
+
     // HACK: Diff doesn't handle mixing content
-    let global_reporter = if args.diff {
+    let format_reporter = if args.diff {
         Box::new(report::PrintSilent)
     } else {
-        args.format.reporter()
+        args.format.reporter(args.column_unit)
+    };
+    let write_baseline = args
+        .write_baseline
+        .as_deref()
+        .or(args.init.as_deref())
+        .map(|path| baseline::WriteBaseline::new(baseline::root(path), format_reporter.as_ref()));
+    let global_reporter: &dyn Report = match write_baseline.as_ref() {
+        Some(write_baseline) => write_baseline,
+        None => format_reporter.as_ref(),
+    };
+    let stats = args.stats.then(|| stats::Stats::new(global_reporter));
+    let global_reporter: &dyn Report = match stats.as_ref() {
+        Some(stats) => stats,
+        None => global_reporter,
+    };
+
+    let prompt = interactive::Prompt::new(
+        Box::new(BufReader::new(std::io::stdin())),
+        Box::new(anstream::stderr()),
+    );
+    let interactive_fix = typos_cli::file::InteractiveFixTypos { fixer: &prompt };
+
+    let cache = args
+        .cache_dir
+        .as_deref()
+        .map(typos_cli::file::Cache::new)
+        .transpose()
+        .with_code(proc_exit::sysexits::CANT_CREAT)?;
+    let cached_typos = cache
+        .as_ref()
+        .map(|cache| typos_cli::file::CachedTypos { cache });
+
+    let selected_checks: &dyn typos_cli::file::FileChecker = if args.files {
+        &typos_cli::file::FoundFiles
+    } else if args.file_types {
+        &typos_cli::file::FileTypes
+    } else if args.highlight_identifiers {
+        &typos_cli::file::HighlightIdentifiers
+    } else if args.identifiers {
+        &typos_cli::file::Identifiers
+    } else if args.highlight_words {
+        &typos_cli::file::HighlightWords
+    } else if args.words {
+        &typos_cli::file::Words
+    } else if args.write_changes {
+        &typos_cli::file::FixTypos
+    } else if args.interactive {
+        &interactive_fix
+    } else if args.diff {
+        &typos_cli::file::DiffTypos
+    } else if let Some(cached_typos) = cached_typos.as_ref() {
+        cached_typos
+    } else {
+        &typos_cli::file::Typos
+    };
+    let report_checked = report::ReportChecked {
+        checks: selected_checks,
+    };
+    let selected_checks: &dyn typos_cli::file::FileChecker =
+        if args.format == args::Format::Junit && !args.files {
+            &report_checked
+        } else {
+            selected_checks
+        };
+    let check_stats = stats.as_ref().map(|stats| stats.checker(selected_checks));
+    let selected_checks: &dyn typos_cli::file::FileChecker = match check_stats.as_ref() {
+        Some(check_stats) => check_stats,
+        None => selected_checks,
     };
 
+    let ranks = !(args.files
+        || args.file_types
+        || args.highlight_identifiers
+        || args.identifiers
+        || args.highlight_words
+        || args.words);
+    if ranks {
+        init_word_counts(file_list.as_ref().unwrap_or(&args.path), args, &mut engine)?;
+    }
+
     // Note: file_list and args.path are mutually exclusive, enforced by clap
-    'path: for path in file_list.as_ref().unwrap_or(&args.path) {
+    for path in file_list.as_ref().unwrap_or(&args.path) {
         // Note paths are passed through stdin, `-` is treated like a normal path
         let cwd = if path == std::path::Path::new("-") {
             if args.file_list.is_some() {
@@ -205,105 +417,42 @@ fn run_checks(args: &args::Args) -> proc_exit::ExitResult {
                     "Can't use `-` (stdin) while using `--file_list` provided paths",
                 ));
             };
+            if args.interactive {
+                return Err(proc_exit::sysexits::USAGE_ERR
+                    .with_message("Can't use `-` (stdin) while using `--interactive`"));
+            };
+            if args.watch {
+                return Err(proc_exit::sysexits::USAGE_ERR
+                    .with_message("Can't use `-` (stdin) while using `--watch`"));
+            };
             global_cwd.clone()
-        } else if path.is_file() {
-            let mut cwd = path
-                .canonicalize()
-                .map_err(|err| {
-                    let kind = err.kind();
-                    std::io::Error::new(kind, format!("argument `{}` is not found", path.display()))
-                })
-                .with_code(proc_exit::sysexits::USAGE_ERR)?;
-            cwd.pop();
-            cwd
         } else {
-            path.canonicalize()
-                .map_err(|err| {
-                    let kind = err.kind();
-                    std::io::Error::new(kind, format!("argument `{}` is not found", path.display()))
-                })
-                .with_code(proc_exit::sysexits::USAGE_ERR)?
+            path_cwd(path)?
         };
 
         engine
             .init_dir(&cwd)
             .with_code(proc_exit::sysexits::CONFIG_ERR)?;
+        init_nested(path, &cwd, args, &mut engine)?;
         let walk_policy = engine.walk(&cwd);
 
-        let threads = if path.is_file() || args.sort {
+        // Prompts need a stable order, one at a time
+        let sort = args.sort || args.interactive;
+        let threads = if path.is_file() || sort {
             1
         } else {
             args.threads
         };
         let single_threaded = threads == 1;
+        let excluded = stats.as_ref().map(|stats| stats.excluded());
+        let Some(walk) = build_walk(path, walk_policy, args, threads, sort, excluded)? else {
+            continue;
+        };
 
-        let mut walk = ignore::WalkBuilder::new(path);
-        walk.threads(threads)
-            .skip_stdout(true)
-            .hidden(walk_policy.ignore_hidden())
-            .ignore(walk_policy.ignore_dot())
-            .git_global(walk_policy.ignore_global())
-            .git_ignore(walk_policy.ignore_vcs())
-            .git_exclude(walk_policy.ignore_vcs())
-            .parents(walk_policy.ignore_parent());
-        if args.sort {
-            walk.sort_by_file_name(|a, b| a.cmp(b));
-        }
-        if !walk_policy.extend_exclude.is_empty() {
-            let mut ignores = ignore::gitignore::GitignoreBuilder::new(".");
-            for pattern in walk_policy.extend_exclude.iter() {
-                ignores
-                    .add_line(None, pattern)
-                    .with_code(proc_exit::sysexits::CONFIG_ERR)?;
-            }
-            let ignores = ignores.build().with_code(proc_exit::sysexits::CONFIG_ERR)?;
-            if args.force_exclude {
-                let mut ancestors = path.ancestors().collect::<Vec<_>>();
-                ancestors.reverse();
-                for path in ancestors {
-                    match ignores.matched(path, path.is_dir()) {
-                        ignore::Match::None => {}
-                        ignore::Match::Ignore(_) => continue 'path,
-                        ignore::Match::Whitelist(_) => break,
-                    }
-                }
-            }
-            walk.filter_entry(move |entry| {
-                let path = entry.path();
-                let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
-                let matched = ignores.matched(path, is_dir);
-                log::debug!("match({path:?}, {is_dir}) == {matched:?}");
-                match matched {
-                    ignore::Match::None => true,
-                    ignore::Match::Ignore(_) => false,
-                    ignore::Match::Whitelist(_) => true,
-                }
-            });
-        }
-
-        let status_reporter = report::MessageStatus::new(global_reporter.as_ref());
+        let status_reporter =
+            report::MessageStatus::new(global_reporter).baseline(baseline_filter.as_ref());
         let reporter: &dyn Report = &status_reporter;
 
-        let selected_checks: &dyn typos_cli::file::FileChecker = if args.files {
-            &typos_cli::file::FoundFiles
-        } else if args.file_types {
-            &typos_cli::file::FileTypes
-        } else if args.highlight_identifiers {
-            &typos_cli::file::HighlightIdentifiers
-        } else if args.identifiers {
-            &typos_cli::file::Identifiers
-        } else if args.highlight_words {
-            &typos_cli::file::HighlightWords
-        } else if args.words {
-            &typos_cli::file::Words
-        } else if args.write_changes {
-            &typos_cli::file::FixTypos
-        } else if args.diff {
-            &typos_cli::file::DiffTypos
-        } else {
-            &typos_cli::file::Typos
-        };
-
         if single_threaded {
             typos_cli::file::walk_path(
                 walk.build(),
@@ -321,17 +470,7 @@ fn run_checks(args: &args::Args) -> proc_exit::ExitResult {
                 args.force_exclude,
             )
         }
-        .map_err(|e| {
-            e.io_error()
-                .map(|i| {
-                    let kind = i.kind();
-                    proc_exit::sysexits::io_to_sysexists(kind)
-                        .or_else(|| proc_exit::bash::io_to_signal(kind))
-                        .unwrap_or(proc_exit::sysexits::IO_ERR)
-                })
-                .unwrap_or_default()
-                .with_message(e)
-        })?;
+        .map_err(walk_error)?;
         if status_reporter.typos_found() {
             typos_found = true;
         }
@@ -345,6 +484,53 @@ fn run_checks(args: &args::Args) -> proc_exit::ExitResult {
         log::error!("could not render end-report: {err}");
     }
 
+    if let Some(stats) = stats.as_ref() {
+        stats.print(args).with_code(proc_exit::sysexits::IO_ERR)?;
+    }
+
+    if let Some(baseline_filter) = baseline_filter.as_ref() {
+        let unused = baseline_filter.unused(file_list.as_ref().unwrap_or(&args.path));
+        for entry in unused.iter() {
+            let level = if args.strict_baseline {
+                log::Level::Error
+            } else {
+                log::Level::Warn
+            };
+            log::log!(
+                level,
+                "{}: `{}` is no longer found and can be removed from the baseline",
+                entry.path,
+                entry.typo
+            );
+        }
+        if args.strict_baseline && !unused.is_empty() {
+            errors_found = true;
+        }
+    }
+    if args.watch {
+        return watch::run(
+            file_list.as_ref().unwrap_or(&args.path),
+            args,
+            &mut engine,
+            selected_checks,
+            global_reporter,
+            baseline.as_ref().zip(baseline_root.as_deref()),
+        );
+    }
+
+    if let Some(write_baseline) = write_baseline {
+        let recorded = write_baseline.into_baseline();
+        if let Some(path) = args.write_baseline.as_deref() {
+            recorded
+                .to_file(path)
+                .with_code(proc_exit::sysexits::IO_ERR)?;
+        } else if let Some(path) = args.init.as_deref() {
+            write_init(path, &recorded.typos, &engine)?;
+        }
+        // Recorded typos are accepted, rather than failures
+        typos_found = false;
+    }
+
     if errors_found {
         proc_exit::Code::FAILURE.ok()
     } else if typos_found {
@@ -358,6 +544,184 @@ fn run_checks(args: &args::Args) -> proc_exit::ExitResult {
     }
 }
 
+/// The directory to load config from for `path`
+fn path_cwd(path: &std::path::Path) -> Result<PathBuf, proc_exit::Exit> {
+    let mut cwd = path
+        .canonicalize()
+        .map_err(|err| {
+            let kind = err.kind();
+            std::io::Error::new(kind, format!("argument `{}` is not found", path.display()))
+        })
+        .with_code(proc_exit::sysexits::USAGE_ERR)?;
+    if path.is_file() {
+        cwd.pop();
+    }
+    Ok(cwd)
+}
+
+/// Load the configs nested under `path`, skipping what the walk for checking it would
+fn init_nested(
+    path: &std::path::Path,
+    cwd: &std::path::Path,
+    args: &args::Args,
+    engine: &mut typos_cli::policy::ConfigEngine<'_>,
+) -> Result<(), proc_exit::Exit> {
+    if !path.is_dir() {
+        return Ok(());
+    }
+    let walk_policy = engine.walk(cwd);
+    if let Some(walk) = build_walk(path, walk_policy, args, 1, false, None)? {
+        engine
+            .init_nested(walk.build())
+            .with_code(proc_exit::sysexits::CONFIG_ERR)?;
+    }
+    Ok(())
+}
+
+/// Count words across `paths` for `rank-corrections`, when any of their files have it set
+fn init_word_counts(
+    paths: &[PathBuf],
+    args: &args::Args,
+    engine: &mut typos_cli::policy::ConfigEngine<'_>,
+) -> Result<(), proc_exit::Exit> {
+    let mut counts = None;
+    for path in paths {
+        if path == std::path::Path::new("-") {
+            continue;
+        }
+        let cwd = path_cwd(path)?;
+        engine
+            .init_dir(&cwd)
+            .with_code(proc_exit::sysexits::CONFIG_ERR)?;
+        init_nested(path, &cwd, args, engine)?;
+        if !engine.ranks_corrections(&cwd) {
+            continue;
+        }
+        let walk_policy = engine.walk(&cwd);
+        let Some(walk) = build_walk(path, walk_policy, args, args.threads, false, None)? else {
+            continue;
+        };
+        let path_counts = typos_cli::file::count_words(walk.build_parallel(), engine);
+        counts
+            .get_or_insert_with(typos_cli::dict::RepoWordCounts::default)
+            .extend(path_counts);
+    }
+    engine.set_word_counts(counts);
+    Ok(())
+}
+
+/// Walk `path` per `walk_policy`, or `None` if it is excluded
+fn build_walk(
+    path: &std::path::Path,
+    walk_policy: &typos_cli::config::Walk,
+    args: &args::Args,
+    threads: usize,
+    sort: bool,
+    excluded: Option<Arc<AtomicUsize>>,
+) -> Result<Option<ignore::WalkBuilder>, proc_exit::Exit> {
+    let mut walk = ignore::WalkBuilder::new(path);
+    walk.threads(threads)
+        .skip_stdout(true)
+        .hidden(walk_policy.ignore_hidden())
+        .ignore(walk_policy.ignore_dot())
+        .git_global(walk_policy.ignore_global())
+        .git_ignore(walk_policy.ignore_vcs())
+        .git_exclude(walk_policy.ignore_vcs())
+        .parents(walk_policy.ignore_parent());
+    if sort {
+        walk.sort_by_file_name(|a, b| a.cmp(b));
+    }
+    if !walk_policy.extend_exclude.is_empty() {
+        let mut ignores = ignore::gitignore::GitignoreBuilder::new(".");
+        for pattern in walk_policy.extend_exclude.iter() {
+            ignores
+                .add_line(None, pattern)
+                .with_code(proc_exit::sysexits::CONFIG_ERR)?;
+        }
+        let ignores = ignores.build().with_code(proc_exit::sysexits::CONFIG_ERR)?;
+        if args.force_exclude {
+            let mut ancestors = path.ancestors().collect::<Vec<_>>();
+            ancestors.reverse();
+            for path in ancestors {
+                match ignores.matched(path, path.is_dir()) {
+                    ignore::Match::None => {}
+                    ignore::Match::Ignore(_) => {
+                        if let Some(excluded) = excluded {
+                            excluded.fetch_add(1, Ordering::Relaxed);
+                        }
+                        return Ok(None);
+                    }
+                    ignore::Match::Whitelist(_) => break,
+                }
+            }
+        }
+        walk.filter_entry(move |entry| {
+            let path = entry.path();
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            let matched = ignores.matched(path, is_dir);
+            log::debug!("match({path:?}, {is_dir}) == {matched:?}");
+            match matched {
+                ignore::Match::None => true,
+                ignore::Match::Ignore(_) => {
+                    if let Some(excluded) = excluded.as_ref() {
+                        excluded.fetch_add(1, Ordering::Relaxed);
+                    }
+                    false
+                }
+                ignore::Match::Whitelist(_) => true,
+            }
+        });
+    }
+    Ok(Some(walk))
+}
+
+fn walk_error(e: ignore::Error) -> proc_exit::Exit {
+    e.io_error()
+        .map(|i| {
+            let kind = i.kind();
+            proc_exit::sysexits::io_to_sysexists(kind)
+                .or_else(|| proc_exit::bash::io_to_signal(kind))
+                .unwrap_or(proc_exit::sysexits::IO_ERR)
+        })
+        .unwrap_or_default()
+        .with_message(e)
+}
+
+fn write_init(
+    path: &std::path::Path,
+    typos: &[baseline::Entry],
+    engine: &typos_cli::policy::ConfigEngine<'_>,
+) -> Result<(), proc_exit::Exit> {
+    let root = baseline::root(path);
+    let mut proposals = init::Proposals::new(typos, |path| {
+        let path = root.join(path).canonicalize().ok()?;
+        engine.policy(&path).file_type.map(ToOwned::to_owned)
+    });
+    if std::io::stdin().is_terminal() && std::io::stderr().is_terminal() {
+        proposals
+            .prompt(&mut std::io::stdin().lock(), &mut std::io::stderr().lock())
+            .with_code(proc_exit::sysexits::IO_ERR)?;
+    }
+    let written = proposals
+        .write(path)
+        .with_code(proc_exit::sysexits::CANT_CREAT)?;
+
+    let mut stderr = std::io::stderr().lock();
+    proposals
+        .summarize(&mut stderr)
+        .with_code(proc_exit::sysexits::IO_ERR)?;
+    if written {
+        let _ = writeln!(stderr, "wrote `{}`", path.display());
+    } else {
+        let _ = writeln!(
+            stderr,
+            "no typos to accept, `{}` not written",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
 fn init_logging(level: Option<log::Level>) {
     if let Some(level) = level {
         let mut builder = env_logger::Builder::new();
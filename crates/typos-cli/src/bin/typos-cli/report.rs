@@ -13,15 +13,17 @@ use annotate_snippets::Origin;
 use annotate_snippets::Snippet;
 use anstream::stderr;
 use anstream::stdout;
+use itertools::Itertools as _;
 use serde_sarif::sarif;
 use serde_sarif::sarif::{ArtifactChange, ArtifactContent, Fix, Replacement};
-use typos_cli::report::{Context, Error, Message, Report, Typo};
+use typos_cli::report::{ColumnUnit, Context, Error, Message, Report, Typo};
 
 const INFO: anstyle::Style = anstyle::AnsiColor::BrightBlue.on_default();
 
 pub(crate) struct MessageStatus<'r> {
     typos_found: atomic::AtomicBool,
     errors_found: atomic::AtomicBool,
+    baseline: Option<&'r crate::baseline::BaselineFilter>,
     reporter: &'r dyn Report,
 }
 
@@ -30,10 +32,20 @@ impl<'r> MessageStatus<'r> {
         Self {
             typos_found: atomic::AtomicBool::new(false),
             errors_found: atomic::AtomicBool::new(false),
+            baseline: None,
             reporter,
         }
     }
 
+    /// Drop typos that were accepted in the baseline
+    pub(crate) fn baseline(
+        mut self,
+        baseline: Option<&'r crate::baseline::BaselineFilter>,
+    ) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
     pub(crate) fn typos_found(&self) -> bool {
         self.typos_found.load(atomic::Ordering::Relaxed)
     }
@@ -45,6 +57,9 @@ impl<'r> MessageStatus<'r> {
 
 impl Report for MessageStatus<'_> {
     fn report(&self, msg: Message<'_>) -> Result<(), std::io::Error> {
+        if self.baseline.is_some_and(|b| b.matches(&msg)) {
+            return Ok(());
+        }
         if msg.is_typo() {
             self.typos_found.store(true, atomic::Ordering::Relaxed);
         }
@@ -77,6 +92,12 @@ impl Report for PrintBrief {
             Message::BinaryFile(msg) => {
                 log::info!("{msg}");
             }
+            Message::Skipped(msg) => {
+                log::info!("{msg}");
+            }
+            Message::UnusedDirective(msg) => {
+                log::warn!("{msg}");
+            }
             Message::Typo(msg) => {
                 let report = &[typo_to_group(msg)];
                 writeln!(stdout(), "{}", renderer.render(report))?;
@@ -115,6 +136,12 @@ impl Report for PrintLong {
             Message::BinaryFile(msg) => {
                 log::info!("{msg}");
             }
+            Message::Skipped(msg) => {
+                log::info!("{msg}");
+            }
+            Message::UnusedDirective(msg) => {
+                log::warn!("{msg}");
+            }
             Message::Typo(msg) => {
                 let report = &[typo_to_group(msg)];
                 writeln!(stdout(), "{}", RENDERER.render(report))?;
@@ -145,25 +172,19 @@ impl Report for PrintLong {
     }
 }
 
+/// Render a typo like [`PrintLong`] does
+pub(crate) fn render_typo(msg: &Typo<'_>) -> String {
+    RENDERER.render(&[typo_to_group(msg)])
+}
+
 fn typo_to_group<'t>(msg: &'t Typo<'t>) -> Group<'t> {
-    let title = match &msg.corrections {
-        typos::Status::Valid => unimplemented!("never valid words to report"),
-        typos::Status::Invalid => {
-            format!("`{}` is disallowed", msg.typo,)
-        }
-        typos::Status::Corrections(corrections) => {
-            format!(
-                "`{}` should be {}",
-                msg.typo,
-                itertools::join(corrections.iter().map(|s| format!("`{s}`")), ", ")
-            )
-        }
-    };
+    let title =
+        typo_to_message(msg).unwrap_or_else(|| unimplemented!("never valid words to report"));
     let group = Group::with_title(Level::ERROR.primary_title(Cow::Owned(title)));
     let group = match &msg.context {
         Some(Context::File(context)) => {
             let path = context.path.as_os_str().to_string_lossy();
-            let (line, span) = to_string(&msg.buffer, msg.byte_offset, msg.typo.len());
+            let (line, span) = msg.line();
             let snippet = Snippet::source(line)
                 .path(path)
                 .line_start(context.line_num);
@@ -177,7 +198,8 @@ fn typo_to_group<'t>(msg: &'t Typo<'t>) -> Group<'t> {
             let parent_len = line.len();
             let mut line = line.into_bytes();
             line.extend(msg.buffer.iter());
-            let (line, span) = to_string(&line, parent_len + msg.byte_offset, msg.typo.len());
+            let (line, span) =
+                typos_cli::report::lossy_span(&line, parent_len + msg.byte_offset, msg.typo.len());
             let line = line.into_owned();
             let snippet = Snippet::source(line);
             append_corrections(span, snippet, group)
@@ -196,31 +218,6 @@ fn append_corrections<'t>(
     group.element(snippet)
 }
 
-fn to_string(line: &[u8], start: usize, len: usize) -> (Cow<'_, str>, Range<usize>) {
-    let end = start + len;
-
-    if let Ok(line) = std::str::from_utf8(line) {
-        return (Cow::Borrowed(line), start..end);
-    }
-
-    let prefix = &line[0..start];
-    let prefix = String::from_utf8_lossy(prefix);
-
-    let middle = &line[start..end];
-    let middle = String::from_utf8_lossy(middle);
-
-    let suffix = &line[end..];
-    let suffix = String::from_utf8_lossy(suffix);
-
-    let span_start = prefix.len();
-    let span_end = span_start + middle.len();
-
-    (
-        Cow::Owned(format!("{prefix}{middle}{suffix}")),
-        span_start..span_end,
-    )
-}
-
 fn error_to_group<'e>(error: &'e Error<'e>) -> Group<'e> {
     let group = Group::with_title(Level::ERROR.primary_title(&error.msg));
     match &error.context {
@@ -249,27 +246,62 @@ static RENDERER: std::sync::LazyLock<annotate_snippets::Renderer> =
             .decor_style(decor_style)
     });
 
-#[derive(Copy, Clone, Debug)]
-pub(crate) struct PrintJson;
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct PrintJson {
+    pub(crate) column_unit: ColumnUnit,
+}
 
 impl Report for PrintJson {
     fn report(&self, msg: Message<'_>) -> Result<(), std::io::Error> {
+        let position = match &msg {
+            Message::Typo(typo) => typo.offset().map(|offset| {
+                let columns = typo.columns(self.column_unit);
+                JsonPosition {
+                    column: columns.start,
+                    end_column: columns.end,
+                    offset,
+                }
+            }),
+            _ => None,
+        };
+        let msg = JsonMessage {
+            msg: &msg,
+            position,
+        };
         writeln!(stdout().lock(), "{}", serde_json::to_string(&msg).unwrap())?;
         Ok(())
     }
 }
 
-#[derive(Debug)]
+#[derive(serde::Serialize)]
+struct JsonMessage<'m> {
+    #[serde(flatten)]
+    msg: &'m Message<'m>,
+    #[serde(flatten)]
+    position: Option<JsonPosition>,
+}
+
+/// Where a typo is within a file, so consumers don't need to convert `byte_offset`
+#[derive(serde::Serialize)]
+struct JsonPosition {
+    column: usize,
+    end_column: usize,
+    /// Byte offset within the file
+    offset: usize,
+}
+
+#[derive(Debug, Default)]
 pub(crate) struct PrintSarif {
+    column_unit: ColumnUnit,
     results: Mutex<Vec<sarif::Result>>,
     error: Mutex<Vec<String>>,
 }
 
-impl Default for PrintSarif {
-    fn default() -> Self {
+impl PrintSarif {
+    pub(crate) fn new(column_unit: ColumnUnit) -> Self {
         Self {
-            results: Mutex::new(Vec::new()),
-            error: Mutex::new(Vec::new()),
+            column_unit,
+            ..Default::default()
         }
     }
 }
@@ -291,8 +323,8 @@ impl PrintSarif {
                 if msg.corrections.is_valid() {
                     return Ok(());
                 }
-                let message = type_to_sarif_message(msg).unwrap();
-                let location = typo_to_sarif_location(msg)?;
+                let message = typo_to_message(msg).unwrap();
+                let location = typo_to_sarif_location(msg, self.column_unit)?;
 
                 let fix =
                     typo_to_sarif_fix(message.clone(), msg.corrections.clone(), location.clone())?;
@@ -303,7 +335,7 @@ impl PrintSarif {
             Message::Error(msg) => {
                 self.error.lock().unwrap().push(msg.msg.clone());
             }
-            Message::BinaryFile(_) => {}
+            Message::BinaryFile(_) | Message::Skipped(_) | Message::UnusedDirective(_) => {}
             Message::Parse(_) | Message::FileType(_) | Message::File(_) => {}
             _ => unimplemented!("New message {:?}", msg),
         }
@@ -323,7 +355,7 @@ impl PrintSarif {
 
         let run_builder = sarif::Run::builder()
             .tool(tool)
-            .column_kind(sarif::ResultColumnKind::UnicodeCodePoints.to_string())
+            .column_kind(sarif_column_kind(self.column_unit).to_string())
             .results(self.results.lock().unwrap().clone());
 
         let run = if !self.error.lock().unwrap().is_empty() {
@@ -359,6 +391,572 @@ impl PrintSarif {
     }
 }
 
+/// A finding for reporters that render everything at the end, in a stable order
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Finding {
+    /// Relative path, with `/` separators
+    path: String,
+    line: Option<usize>,
+    column: Option<usize>,
+    severity: Severity,
+    message: String,
+    fingerprint: String,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Severity {
+    /// Skipped, like binary files
+    Info,
+    Typo,
+    /// Failed to check, like unreadable files
+    Error,
+}
+
+impl Finding {
+    fn from_message(msg: &Message<'_>, column_unit: ColumnUnit) -> Option<Self> {
+        let finding = match msg {
+            Message::Typo(msg) => {
+                let message = typo_to_message(msg)?;
+                let (line, column) = match typo_to_span(msg, column_unit) {
+                    Some(span) => (Some(span.line), Some(span.start_column)),
+                    None => (None, None),
+                };
+                Self {
+                    path: finding_path(context_path(msg.context.as_ref())),
+                    line,
+                    column,
+                    severity: Severity::Typo,
+                    message,
+                    fingerprint: msg.fingerprint(),
+                }
+            }
+            Message::BinaryFile(msg) => Self {
+                path: finding_path(msg.path),
+                line: None,
+                column: None,
+                severity: Severity::Info,
+                message: msg.to_string(),
+                fingerprint: msg.fingerprint(),
+            },
+            Message::Skipped(msg) => {
+                if let Some(Context::File(_)) = &msg.context {
+                    // Skipped lines are too noisy to annotate
+                    log::info!("{msg}");
+                    return None;
+                }
+                Self {
+                    path: finding_path(context_path(msg.context.as_ref())),
+                    line: None,
+                    column: None,
+                    severity: Severity::Info,
+                    message: msg.to_string(),
+                    fingerprint: msg.fingerprint(),
+                }
+            }
+            Message::UnusedDirective(msg) => Self {
+                path: finding_path(context_path(msg.context.as_ref())),
+                line: match &msg.context {
+                    Some(Context::File(context)) => Some(context.line_num),
+                    _ => None,
+                },
+                column: None,
+                severity: Severity::Info,
+                message: msg.to_string(),
+                fingerprint: msg.fingerprint(),
+            },
+            Message::Error(msg) => Self {
+                path: finding_path(context_path(msg.context.as_ref())),
+                line: match &msg.context {
+                    Some(Context::File(context)) => Some(context.line_num),
+                    _ => None,
+                },
+                column: None,
+                severity: Severity::Error,
+                message: msg.msg.clone(),
+                fingerprint: msg.fingerprint(),
+            },
+            Message::Parse(_) | Message::FileType(_) | Message::File(_) => return None,
+            _ => unimplemented!("New message {:?}", msg),
+        };
+        Some(finding)
+    }
+
+    /// Sort, making fingerprints unique for repeated findings
+    fn finalize(findings: &Mutex<Vec<Self>>) -> Vec<Self> {
+        let mut findings = findings.lock().unwrap().clone();
+        findings.sort();
+        let mut seen = std::collections::HashMap::<String, usize>::new();
+        for finding in findings.iter_mut() {
+            let count = seen.entry(finding.fingerprint.clone()).or_default();
+            *count += 1;
+            if 1 < *count {
+                finding.fingerprint = format!("{}-{count}", finding.fingerprint);
+            }
+        }
+        findings
+    }
+}
+
+fn context_path<'c>(context: Option<&Context<'c>>) -> &'c std::path::Path {
+    match context {
+        Some(Context::File(ctx)) => ctx.path,
+        Some(Context::Path(ctx)) => ctx.path,
+        _ => std::path::Path::new(""),
+    }
+}
+
+fn finding_path(path: &std::path::Path) -> String {
+    let path = path.strip_prefix(".").unwrap_or(path);
+    path.display()
+        .to_string()
+        .replace(std::path::MAIN_SEPARATOR, "/")
+}
+
+/// [GitLab Code Quality](https://docs.gitlab.com/ci/testing/code_quality/) report
+#[derive(Debug, Default)]
+pub(crate) struct PrintGitlab {
+    findings: Mutex<Vec<Finding>>,
+}
+
+impl Report for PrintGitlab {
+    fn report(&self, msg: Message<'_>) -> Result<(), std::io::Error> {
+        // Only lines are reported
+        if let Some(finding) = Finding::from_message(&msg, ColumnUnit::default()) {
+            self.findings.lock().unwrap().push(finding);
+        }
+        Ok(())
+    }
+
+    fn generate_final_result(&self) -> Result<(), std::io::Error> {
+        let issues: Vec<_> = Finding::finalize(&self.findings)
+            .into_iter()
+            .map(|finding| {
+                let severity = match finding.severity {
+                    Severity::Info => "info",
+                    Severity::Typo => "minor",
+                    Severity::Error => "critical",
+                };
+                GitlabIssue {
+                    description: finding.message,
+                    check_name: "typos",
+                    fingerprint: finding.fingerprint,
+                    severity,
+                    location: GitlabLocation {
+                        path: finding.path,
+                        lines: GitlabLines {
+                            begin: finding.line.unwrap_or(1),
+                        },
+                    },
+                }
+            })
+            .collect();
+        let mut stdout = stdout().lock();
+        serde_json::to_writer_pretty(&mut stdout, &issues)?;
+        writeln!(stdout)?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, serde::Serialize)]
+struct GitlabIssue {
+    description: String,
+    check_name: &'static str,
+    fingerprint: String,
+    severity: &'static str,
+    location: GitlabLocation,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct GitlabLocation {
+    path: String,
+    lines: GitlabLines,
+}
+
+#[derive(Debug, serde::Serialize)]
+struct GitlabLines {
+    begin: usize,
+}
+
+/// [Checkstyle](https://checkstyle.org/) XML report
+#[derive(Debug, Default)]
+pub(crate) struct PrintCheckstyle {
+    column_unit: ColumnUnit,
+    findings: Mutex<Vec<Finding>>,
+}
+
+impl PrintCheckstyle {
+    pub(crate) fn new(column_unit: ColumnUnit) -> Self {
+        Self {
+            column_unit,
+            ..Default::default()
+        }
+    }
+}
+
+impl Report for PrintCheckstyle {
+    fn report(&self, msg: Message<'_>) -> Result<(), std::io::Error> {
+        if let Some(finding) = Finding::from_message(&msg, self.column_unit) {
+            self.findings.lock().unwrap().push(finding);
+        }
+        Ok(())
+    }
+
+    fn generate_final_result(&self) -> Result<(), std::io::Error> {
+        let findings = Finding::finalize(&self.findings);
+        let mut stdout = stdout().lock();
+        writeln!(stdout, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(stdout, r#"<checkstyle version="4.3">"#)?;
+        for (path, findings) in &findings.iter().chunk_by(|finding| finding.path.as_str()) {
+            writeln!(stdout, r#"  <file name="{}">"#, xml_escape(path))?;
+            for finding in findings {
+                let severity = match finding.severity {
+                    Severity::Info => "info",
+                    Severity::Typo | Severity::Error => "error",
+                };
+                write!(stdout, "    <error")?;
+                if let Some(line) = finding.line {
+                    write!(stdout, r#" line="{line}""#)?;
+                }
+                if let Some(column) = finding.column {
+                    write!(stdout, r#" column="{column}""#)?;
+                }
+                writeln!(
+                    stdout,
+                    r#" severity="{severity}" message="{}" source="typos"/>"#,
+                    xml_escape(&finding.message),
+                )?;
+            }
+            writeln!(stdout, "  </file>")?;
+        }
+        writeln!(stdout, "</checkstyle>")?;
+        Ok(())
+    }
+}
+
+/// GitHub Actions [workflow commands](https://docs.github.com/en/actions/reference/workflows-and-actions/workflow-commands)
+///
+/// Typos are printed as warning annotations, followed by the same report as the default format,
+/// with a summary of all typos appended to `$GITHUB_STEP_SUMMARY` at the end.
+#[derive(Debug, Default)]
+pub(crate) struct PrintGithub {
+    column_unit: ColumnUnit,
+    typos: Mutex<Vec<GithubTypo>>,
+}
+
+impl PrintGithub {
+    pub(crate) fn new(column_unit: ColumnUnit) -> Self {
+        Self {
+            column_unit,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct GithubTypo {
+    path: String,
+    line: Option<usize>,
+    typo: String,
+    corrections: Vec<String>,
+}
+
+impl Report for PrintGithub {
+    fn report(&self, msg: Message<'_>) -> Result<(), std::io::Error> {
+        match &msg {
+            Message::Typo(msg) => {
+                let Some(message) = typo_to_message(msg) else {
+                    return Ok(());
+                };
+                let path = github_path(context_path(msg.context.as_ref()));
+                let mut properties = vec![("file", path.clone())];
+                let span = typo_to_span(msg, self.column_unit);
+                if let Some(span) = &span {
+                    properties.extend([
+                        ("line", span.line.to_string()),
+                        ("col", span.start_column.to_string()),
+                        ("endColumn", span.end_column.to_string()),
+                    ]);
+                }
+                properties.push(("title", "typo".to_owned()));
+                write_workflow_command("warning", &properties, &message)?;
+                // Workflow commands are hidden in the job log, so also print the usual report
+                PrintLong.report(Message::Typo(msg.clone()))?;
+
+                let corrections = match &msg.corrections {
+                    typos::Status::Corrections(corrections) => {
+                        corrections.iter().map(|c| c.to_string()).collect()
+                    }
+                    _ => Vec::new(),
+                };
+                self.typos.lock().unwrap().push(GithubTypo {
+                    path,
+                    line: span.map(|span| span.line),
+                    typo: msg.typo.to_owned(),
+                    corrections,
+                });
+            }
+            Message::Error(msg) => {
+                let mut properties = Vec::new();
+                if msg.context.is_some() {
+                    properties.push(("file", github_path(context_path(msg.context.as_ref()))));
+                }
+                if let Some(Context::File(context)) = &msg.context {
+                    properties.push(("line", context.line_num.to_string()));
+                }
+                write_workflow_command("error", &properties, &msg.msg)?;
+            }
+            Message::BinaryFile(msg) => {
+                log::info!("{msg}");
+            }
+            Message::Skipped(msg) => {
+                log::info!("{msg}");
+            }
+            Message::UnusedDirective(msg) => {
+                log::warn!("{msg}");
+            }
+            Message::FileType(_) | Message::File(_) | Message::Parse(_) => {
+                PrintBrief.report(msg)?;
+            }
+            _ => unimplemented!("New message {:?}", msg),
+        }
+        Ok(())
+    }
+
+    fn generate_final_result(&self) -> Result<(), std::io::Error> {
+        let Some(summary_path) = std::env::var_os("GITHUB_STEP_SUMMARY") else {
+            return Ok(());
+        };
+        let mut typos = self.typos.lock().unwrap().clone();
+        typos.sort();
+
+        let mut summary = String::from("## typos\n\n");
+        if typos.is_empty() {
+            summary.push_str("No typos found\n");
+        } else {
+            let files = typos.iter().map(|typo| &typo.path).dedup().count();
+            let plural = |count, noun| {
+                if count == 1 {
+                    format!("{count} {noun}")
+                } else {
+                    format!("{count} {noun}s")
+                }
+            };
+            summary.push_str(&format!(
+                "Found {} in {}\n\n",
+                plural(typos.len(), "typo"),
+                plural(files, "file"),
+            ));
+            summary.push_str("| File | Line | Typo | Corrections |\n");
+            summary.push_str("| ---- | ---- | ---- | ----------- |\n");
+            for typo in typos {
+                let line = typo.line.map(|l| l.to_string()).unwrap_or_default();
+                let corrections = if typo.corrections.is_empty() {
+                    "*disallowed*".to_owned()
+                } else {
+                    itertools::join(typo.corrections.iter().map(|c| markdown_code(c)), ", ")
+                };
+                summary.push_str(&format!(
+                    "| {} | {line} | {} | {corrections} |\n",
+                    markdown_code(&typo.path),
+                    markdown_code(&typo.typo),
+                ));
+            }
+        }
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(summary_path)?;
+        file.write_all(summary.as_bytes())
+    }
+}
+
+/// Paths relative to the workspace, as annotations require
+fn github_path(path: &std::path::Path) -> String {
+    let workspace = std::env::var_os("GITHUB_WORKSPACE").map(std::path::PathBuf::from);
+    let path = workspace
+        .and_then(|workspace| {
+            let path = std::path::absolute(path).ok()?;
+            path.strip_prefix(workspace).ok().map(|p| p.to_owned())
+        })
+        .unwrap_or_else(|| path.to_owned());
+    finding_path(&path)
+}
+
+fn write_workflow_command(
+    command: &str,
+    properties: &[(&str, String)],
+    message: &str,
+) -> Result<(), std::io::Error> {
+    let escape_data = |data: &str| {
+        data.replace('%', "%25")
+            .replace('\r', "%0D")
+            .replace('\n', "%0A")
+    };
+    let properties = itertools::join(
+        properties.iter().map(|(key, value)| {
+            let value = escape_data(value).replace(':', "%3A").replace(',', "%2C");
+            format!("{key}={value}")
+        }),
+        ",",
+    );
+    let separator = if properties.is_empty() { "" } else { " " };
+    writeln!(
+        stdout().lock(),
+        "::{command}{separator}{properties}::{}",
+        escape_data(message)
+    )
+}
+
+/// Inline code, safe for use in a table cell
+fn markdown_code(text: &str) -> String {
+    let text = text.replace('|', "\\|");
+    let fence = if text.contains('`') { "`` " } else { "`" };
+    let end = if text.contains('`') { " ``" } else { "`" };
+    format!("{fence}{text}{end}")
+}
+
+/// [JUnit](https://github.com/testmoapp/junitxml) XML report, with each file as a testcase
+///
+/// Files without typos are only included when reported with [`Message::File`], see
+/// [`ReportChecked`].
+#[derive(Debug, Default)]
+pub(crate) struct PrintJunit {
+    column_unit: ColumnUnit,
+    findings: Mutex<Vec<Finding>>,
+    checked: Mutex<Vec<String>>,
+}
+
+impl PrintJunit {
+    pub(crate) fn new(column_unit: ColumnUnit) -> Self {
+        Self {
+            column_unit,
+            ..Default::default()
+        }
+    }
+}
+
+impl Report for PrintJunit {
+    fn report(&self, msg: Message<'_>) -> Result<(), std::io::Error> {
+        if let Message::File(msg) = &msg {
+            self.checked.lock().unwrap().push(finding_path(msg.path));
+        } else if let Some(finding) = Finding::from_message(&msg, self.column_unit) {
+            self.findings.lock().unwrap().push(finding);
+        }
+        Ok(())
+    }
+
+    fn generate_final_result(&self) -> Result<(), std::io::Error> {
+        let mut testcases = std::collections::BTreeMap::<String, Vec<Finding>>::new();
+        for path in self.checked.lock().unwrap().iter() {
+            testcases.entry(path.clone()).or_default();
+        }
+        for finding in Finding::finalize(&self.findings) {
+            testcases
+                .entry(finding.path.clone())
+                .or_default()
+                .push(finding);
+        }
+
+        let count = |severity| {
+            testcases
+                .values()
+                .filter(|findings| findings.iter().map(|f| f.severity).max() == Some(severity))
+                .count()
+        };
+        let counts = format!(
+            r#"tests="{}" failures="{}" errors="{}" skipped="{}""#,
+            testcases.len(),
+            count(Severity::Typo),
+            count(Severity::Error),
+            count(Severity::Info),
+        );
+
+        let mut stdout = stdout().lock();
+        writeln!(stdout, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(stdout, r#"<testsuites name="typos" {counts}>"#)?;
+        writeln!(stdout, r#"  <testsuite name="typos" {counts}>"#)?;
+        for (path, findings) in testcases.iter() {
+            let name = if path.is_empty() { "typos" } else { path };
+            let name = xml_escape(name);
+            let Some(severity) = findings.iter().map(|f| f.severity).max() else {
+                writeln!(stdout, r#"    <testcase name="{name}" classname="typos"/>"#)?;
+                continue;
+            };
+            writeln!(stdout, r#"    <testcase name="{name}" classname="typos">"#)?;
+            let (element, kind) = match severity {
+                Severity::Info => ("skipped", "skipped"),
+                Severity::Typo => ("failure", "typo"),
+                Severity::Error => ("error", "error"),
+            };
+            let findings: Vec<_> = findings.iter().filter(|f| f.severity == severity).collect();
+            let message = match findings.as_slice() {
+                [finding] => finding.message.clone(),
+                _ => format!("{} {kind}s", findings.len()),
+            };
+            let details = itertools::join(
+                findings.iter().map(|finding| {
+                    let mut location = path.clone();
+                    if let Some(line) = finding.line {
+                        location.push_str(&format!(":{line}"));
+                    }
+                    if let Some(column) = finding.column {
+                        location.push_str(&format!(":{column}"));
+                    }
+                    format!("{location}: {}", finding.message)
+                }),
+                "\n",
+            );
+            writeln!(
+                stdout,
+                r#"      <{element} message="{}" type="{kind}">{}</{element}>"#,
+                xml_escape(&message),
+                xml_escape(&details),
+            )?;
+            writeln!(stdout, "    </testcase>")?;
+        }
+        writeln!(stdout, "  </testsuite>")?;
+        writeln!(stdout, "</testsuites>")?;
+        Ok(())
+    }
+}
+
+/// Report each file after checking it, so reporters can list files without typos
+pub(crate) struct ReportChecked<'c> {
+    pub(crate) checks: &'c dyn typos_cli::file::FileChecker,
+}
+
+impl typos_cli::file::FileChecker for ReportChecked<'_> {
+    fn check_file(
+        &self,
+        path: &std::path::Path,
+        explicit: bool,
+        policy: &typos_cli::policy::Policy<'_, '_, '_>,
+        reporter: &dyn Report,
+    ) -> Result<(), std::io::Error> {
+        self.checks.check_file(path, explicit, policy, reporter)?;
+        reporter.report(typos_cli::report::File::new(path).into())
+    }
+}
+
+fn xml_escape(text: &str) -> Cow<'_, str> {
+    if !text.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
 fn sarif_error_mapper(error: impl std::fmt::Display) -> std::io::Error {
     std::io::Error::other(format!("failed to generate SARIF output: {error}"))
 }
@@ -420,19 +1018,59 @@ fn typo_to_sarif_fix(
     Ok(Some(fix))
 }
 
-fn type_to_sarif_message(msg: &Typo<'_>) -> Option<String> {
+fn typo_to_message(msg: &Typo<'_>) -> Option<String> {
     match &msg.corrections {
         typos::Status::Valid => None,
         typos::Status::Invalid => Some(format!("`{}` is disallowed", msg.typo)),
-        typos::Status::Corrections(corrections) => Some(format!(
-            "`{}` should be {}",
-            msg.typo,
-            itertools::join(corrections.iter().map(|s| format!("`{s}`")), ", ",)
-        )),
+        typos::Status::Corrections(corrections) => {
+            let mut message = format!(
+                "`{}` should be {}",
+                msg.typo,
+                itertools::join(corrections.iter().map(|s| format!("`{s}`")), ", ",)
+            );
+            if msg.preferred {
+                message.push_str(" (per `preferred-corrections`)");
+            }
+            Some(message)
+        }
     }
 }
 
-fn typo_to_sarif_location(msg: &Typo<'_>) -> Result<sarif::Location, Box<dyn std::error::Error>> {
+/// Where a typo is within its line, with 1-based columns
+struct Span {
+    line: usize,
+    start_column: usize,
+    /// Exclusive
+    end_column: usize,
+    /// Byte offset within the file
+    offset: Option<usize>,
+}
+
+fn typo_to_span(msg: &Typo<'_>, column_unit: ColumnUnit) -> Option<Span> {
+    let Some(Context::File(context)) = &msg.context else {
+        return None;
+    };
+    let columns = msg.columns(column_unit);
+    Some(Span {
+        line: context.line_num,
+        start_column: columns.start,
+        end_column: columns.end,
+        offset: msg.offset(),
+    })
+}
+
+fn sarif_column_kind(column_unit: ColumnUnit) -> sarif::ResultColumnKind {
+    match column_unit {
+        ColumnUnit::Utf16 => sarif::ResultColumnKind::Utf16CodeUnits,
+        // Rejected when parsing args, as SARIF has no byte columns
+        ColumnUnit::Byte | ColumnUnit::Char => sarif::ResultColumnKind::UnicodeCodePoints,
+    }
+}
+
+fn typo_to_sarif_location(
+    msg: &Typo<'_>,
+    column_unit: ColumnUnit,
+) -> Result<sarif::Location, Box<dyn std::error::Error>> {
     let path = match &msg.context {
         Some(Context::File(ctx)) => ctx.path,
         Some(Context::Path(ctx)) => ctx.path,
@@ -449,20 +1087,18 @@ fn typo_to_sarif_location(msg: &Typo<'_>) -> Result<sarif::Location, Box<dyn std
         .build();
     let physical = sarif::PhysicalLocation::builder().artifact_location(artifact);
 
-    if let Some(Context::File(context)) = &msg.context {
-        let start = String::from_utf8_lossy(&msg.buffer[0..msg.byte_offset]);
-        let column_start = start.chars().count() + 1;
-        let column_end = msg.typo.chars().count() + column_start;
-        let line_num = context.line_num;
-
-        let physical = physical.region(
-            sarif::Region::builder()
-                .start_line(line_num as i64)
-                .end_line(line_num as i64)
-                .start_column(column_start as i64)
-                .end_column(column_end as i64)
-                .build(),
-        );
+    if let Some(span) = typo_to_span(msg, column_unit) {
+        let mut region = sarif::Region::builder()
+            .start_line(span.line as i64)
+            .end_line(span.line as i64)
+            .start_column(span.start_column as i64)
+            .end_column(span.end_column as i64)
+            .build();
+        if let Some(offset) = span.offset {
+            region.byte_offset = Some(offset as i64);
+            region.byte_length = Some(msg.typo.len() as i64);
+        }
+        let physical = physical.region(region);
         let location = sarif::Location::builder()
             .physical_location(physical.build())
             .build();
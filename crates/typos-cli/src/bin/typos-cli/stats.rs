@@ -0,0 +1,380 @@
+//! Summarize what was checked and found, for `--stats`
+
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use typos_cli::report::{Context, Message, Report};
+
+/// How many of the most frequent typos to list
+const TOP_TYPOS: usize = 10;
+
+/// Count messages on their way to `reporter`
+pub(crate) struct Stats<'r> {
+    reporter: &'r dyn Report,
+    counts: Mutex<Counts>,
+    /// Shared with the walk, to count entries skipped by `extend-exclude`
+    excluded: Arc<AtomicUsize>,
+}
+
+#[derive(Default)]
+struct Counts {
+    /// Files whose contents were checked
+    files: usize,
+    binary: usize,
+    /// Skipped by `skip-generated` or `max-file-size`
+    skipped: usize,
+    skipped_by_type: usize,
+    typos: usize,
+    fixable: usize,
+    ambiguous: usize,
+    disallowed: usize,
+    /// Occurrences of each typo, ignoring case
+    occurrences: HashMap<String, usize>,
+    file_types: BTreeMap<String, TypeCounts>,
+    /// The file type of each checked file, to attribute typos to
+    paths: HashMap<PathBuf, String>,
+}
+
+#[derive(Copy, Clone, Default, Debug, serde::Serialize)]
+struct TypeCounts {
+    files: usize,
+    typos: usize,
+}
+
+impl<'r> Stats<'r> {
+    pub(crate) fn new(reporter: &'r dyn Report) -> Self {
+        Self {
+            reporter,
+            counts: Default::default(),
+            excluded: Default::default(),
+        }
+    }
+
+    /// Counter for entries excluded while walking
+    pub(crate) fn excluded(&self) -> Arc<AtomicUsize> {
+        self.excluded.clone()
+    }
+
+    /// Count the files checked by `checks`
+    pub(crate) fn checker<'s>(
+        &'s self,
+        checks: &'s dyn typos_cli::file::FileChecker,
+    ) -> CheckStats<'s, 'r> {
+        CheckStats {
+            checks,
+            stats: self,
+        }
+    }
+
+    /// Print as a table or, for `--format json`, as a JSON object
+    ///
+    /// Output formats that can't have a table mixed in have it written to stderr instead.
+    pub(crate) fn print(&self, args: &crate::args::Args) -> Result<(), std::io::Error> {
+        use crate::args::Format;
+
+        match args.format {
+            Format::Json if !args.diff => {
+                writeln!(anstream::stdout().lock(), "{}", self.to_json())
+            }
+            Format::Long | Format::Brief if !args.diff => {
+                self.write_table(&mut anstream::stdout().lock())
+            }
+            _ => self.write_table(&mut anstream::stderr().lock()),
+        }
+    }
+
+    fn top_typos(counts: &Counts) -> Vec<(&str, usize)> {
+        let mut top: Vec<_> = counts
+            .occurrences
+            .iter()
+            .map(|(typo, count)| (typo.as_str(), *count))
+            .collect();
+        top.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        top.truncate(TOP_TYPOS);
+        top
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let counts = self.counts.lock().unwrap();
+        let top_typos: Vec<_> = Self::top_typos(&counts)
+            .into_iter()
+            .map(|(typo, count)| serde_json::json!({"typo": typo, "count": count}))
+            .collect();
+        serde_json::json!({
+            "type": "stats",
+            "files": counts.files,
+            "binary": counts.binary,
+            "skipped": counts.skipped,
+            "skipped_by_type": counts.skipped_by_type,
+            "excluded": self.excluded.load(Ordering::Relaxed),
+            "typos": counts.typos,
+            "fixable": counts.fixable,
+            "ambiguous": counts.ambiguous,
+            "disallowed": counts.disallowed,
+            "top_typos": top_typos,
+            "file_types": counts.file_types,
+        })
+    }
+
+    fn write_table(&self, out: &mut dyn Write) -> Result<(), std::io::Error> {
+        let counts = self.counts.lock().unwrap();
+        let heading = anstyle::Style::new().bold();
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "{heading}Files:{heading:#} {} checked, {} binary, {} generated or too large, {} skipped by type, {} excluded",
+            counts.files,
+            counts.binary,
+            counts.skipped,
+            counts.skipped_by_type,
+            self.excluded.load(Ordering::Relaxed),
+        )?;
+        writeln!(
+            out,
+            "{heading}Typos:{heading:#} {} ({} fixable, {} ambiguous, {} disallowed)",
+            counts.typos, counts.fixable, counts.ambiguous, counts.disallowed,
+        )?;
+
+        let top_typos = Self::top_typos(&counts);
+        if !top_typos.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "{heading}Most frequent typos:{heading:#}")?;
+            let width = top_typos[0].1.to_string().len();
+            for (typo, count) in top_typos {
+                writeln!(out, "  {count:>width$}  {typo}")?;
+            }
+        }
+
+        if !counts.file_types.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "{heading}By file type:{heading:#}")?;
+            let name_width = counts.file_types.keys().map(|t| t.len()).max().unwrap_or(0);
+            let files_width = counts
+                .file_types
+                .values()
+                .map(|c| c.files.to_string().len())
+                .max()
+                .unwrap_or(0);
+            for (file_type, type_counts) in counts.file_types.iter() {
+                writeln!(
+                    out,
+                    "  {file_type:<name_width$}  {:>files_width$} files  {} typos",
+                    type_counts.files, type_counts.typos,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Report for Stats<'_> {
+    fn report(&self, msg: Message<'_>) -> Result<(), std::io::Error> {
+        match &msg {
+            Message::Typo(typo) if !typo.corrections.is_valid() => {
+                let mut counts = self.counts.lock().unwrap();
+                counts.typos += 1;
+                match &typo.corrections {
+                    typos::Status::Corrections(c) if c.len() == 1 => counts.fixable += 1,
+                    typos::Status::Corrections(_) => counts.ambiguous += 1,
+                    _ => counts.disallowed += 1,
+                }
+                *counts
+                    .occurrences
+                    .entry(typo.typo.to_lowercase())
+                    .or_default() += 1;
+                let path = match &typo.context {
+                    Some(Context::File(context)) => Some(context.path),
+                    Some(Context::Path(context)) => Some(context.path),
+                    _ => None,
+                };
+                if let Some(file_type) = path.and_then(|path| counts.paths.get(path)).cloned() {
+                    counts.file_types.entry(file_type).or_default().typos += 1;
+                }
+            }
+            Message::BinaryFile(_) => {
+                self.counts.lock().unwrap().binary += 1;
+            }
+            // Only count whole files, not lines
+            Message::Skipped(msg) if !matches!(msg.context, Some(Context::File(_))) => {
+                self.counts.lock().unwrap().skipped += 1;
+            }
+            _ => {}
+        }
+        self.reporter.report(msg)
+    }
+
+    fn generate_final_result(&self) -> Result<(), std::io::Error> {
+        self.reporter.generate_final_result()
+    }
+}
+
+pub(crate) struct CheckStats<'s, 'r> {
+    checks: &'s dyn typos_cli::file::FileChecker,
+    stats: &'s Stats<'r>,
+}
+
+impl typos_cli::file::FileChecker for CheckStats<'_, '_> {
+    fn check_file(
+        &self,
+        path: &Path,
+        explicit: bool,
+        policy: &typos_cli::policy::Policy<'_, '_, '_>,
+        reporter: &dyn Report,
+    ) -> Result<(), std::io::Error> {
+        let file_type = policy.file_type.unwrap_or("-").to_owned();
+        {
+            let mut counts = self.stats.counts.lock().unwrap();
+            if !policy.check_files {
+                counts.skipped_by_type += 1;
+            }
+            counts.paths.insert(path.to_owned(), file_type.clone());
+        }
+
+        let file_reporter = FileReporter {
+            reporter,
+            unchecked: AtomicBool::new(!policy.check_files),
+        };
+        self.checks
+            .check_file(path, explicit, policy, &file_reporter)?;
+
+        if !file_reporter.unchecked.load(Ordering::Relaxed) {
+            let mut counts = self.stats.counts.lock().unwrap();
+            counts.files += 1;
+            counts.file_types.entry(file_type).or_default().files += 1;
+        }
+        Ok(())
+    }
+}
+
+/// Notice when a file's contents aren't checked, like when it is binary or unreadable
+struct FileReporter<'r> {
+    reporter: &'r dyn Report,
+    unchecked: AtomicBool,
+}
+
+impl Report for FileReporter<'_> {
+    fn report(&self, msg: Message<'_>) -> Result<(), std::io::Error> {
+        let unchecked = match &msg {
+            Message::BinaryFile(_) | Message::Error(_) => true,
+            Message::Skipped(msg) => !matches!(msg.context, Some(Context::File(_))),
+            _ => false,
+        };
+        if unchecked {
+            self.unchecked.store(true, Ordering::Relaxed);
+        }
+        self.reporter.report(msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use typos_cli::report::{FileContext, Typo};
+
+    struct CheckNothing;
+
+    impl typos_cli::file::FileChecker for CheckNothing {
+        fn check_file(
+            &self,
+            _path: &Path,
+            _explicit: bool,
+            _policy: &typos_cli::policy::Policy<'_, '_, '_>,
+            _reporter: &dyn Report,
+        ) -> Result<(), std::io::Error> {
+            Ok(())
+        }
+    }
+
+    struct ReportError;
+
+    impl typos_cli::file::FileChecker for ReportError {
+        fn check_file(
+            &self,
+            _path: &Path,
+            _explicit: bool,
+            _policy: &typos_cli::policy::Policy<'_, '_, '_>,
+            reporter: &dyn Report,
+        ) -> Result<(), std::io::Error> {
+            let msg = typos_cli::report::Error::new("could not read".to_owned());
+            reporter.report(msg.into())
+        }
+    }
+
+    #[test]
+    fn counts_typos_by_file_type() {
+        let reporter = crate::report::PrintSilent;
+        let stats = Stats::new(&reporter);
+        let checks = stats.checker(&CheckNothing);
+        let path = Path::new("README.md");
+        let mut policy = typos_cli::policy::Policy::new();
+        policy.file_type = Some("md");
+        typos_cli::file::FileChecker::check_file(&checks, path, true, &policy, &reporter).unwrap();
+
+        let context = FileContext::default().path(path).line_num(1);
+        for (typo, corrections) in [
+            ("teh", vec!["the"]),
+            ("Teh", vec!["the"]),
+            ("abck", vec!["back", "aback"]),
+        ] {
+            let msg = Typo::default()
+                .context(Some(context.clone().into()))
+                .typo(typo)
+                .corrections(typos::Status::Corrections(
+                    corrections.into_iter().map(Into::into).collect(),
+                ));
+            stats.report(msg.into()).unwrap();
+        }
+
+        let json = stats.to_json();
+        assert_eq!(json["files"], 1);
+        assert_eq!(json["typos"], 3);
+        assert_eq!(json["fixable"], 2);
+        assert_eq!(json["ambiguous"], 1);
+        assert_eq!(
+            json["top_typos"],
+            serde_json::json!([{"typo": "teh", "count": 2}, {"typo": "abck", "count": 1}])
+        );
+        assert_eq!(
+            json["file_types"],
+            serde_json::json!({"md": {"files": 1, "typos": 3}})
+        );
+    }
+
+    #[test]
+    fn only_counts_checked_files() {
+        let reporter = crate::report::PrintSilent;
+        let stats = Stats::new(&reporter);
+        let mut policy = typos_cli::policy::Policy::new();
+
+        let checks = stats.checker(&ReportError);
+        typos_cli::file::FileChecker::check_file(
+            &checks,
+            Path::new("a.txt"),
+            false,
+            &policy,
+            &stats,
+        )
+        .unwrap();
+
+        let checks = stats.checker(&CheckNothing);
+        policy.check_files = false;
+        typos_cli::file::FileChecker::check_file(
+            &checks,
+            Path::new("b.lock"),
+            false,
+            &policy,
+            &stats,
+        )
+        .unwrap();
+
+        let json = stats.to_json();
+        assert_eq!(json["files"], 0);
+        assert_eq!(json["skipped_by_type"], 1);
+        assert_eq!(json["file_types"], serde_json::json!({}));
+    }
+}
@@ -0,0 +1,342 @@
+//! Re-check files as they change, for `--watch`
+
+use std::collections::HashMap;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use proc_exit::prelude::*;
+
+use typos_cli::file::FileChecker;
+use typos_cli::report::Report;
+
+/// How long to wait for more events, since editors often save a file in several steps
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl Stamp {
+    fn new(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// The files to check
+#[derive(Default)]
+struct Snapshot {
+    files: Vec<(ignore::DirEntry, Option<Stamp>)>,
+    /// Indexes into `files` by canonical path, to look up the paths of events
+    index: HashMap<PathBuf, usize>,
+}
+
+impl Snapshot {
+    /// Walk `paths`, with their configs loaded by [`init`]
+    fn scan(
+        paths: &[PathBuf],
+        args: &crate::args::Args,
+        engine: &typos_cli::policy::ConfigEngine<'_>,
+    ) -> Result<Self, proc_exit::Exit> {
+        let mut snapshot = Self::default();
+        for path in paths {
+            let cwd = crate::path_cwd(path)?;
+            let walk_policy = engine.walk(&cwd);
+            let Some(walk) = crate::build_walk(path, walk_policy, args, 1, true, None)? else {
+                continue;
+            };
+            for entry in walk.build() {
+                let Ok(entry) = entry else {
+                    // Reported when checking
+                    continue;
+                };
+                if !entry.file_type().map(|t| t.is_file()).unwrap_or(false) {
+                    continue;
+                }
+                let stamp = Stamp::new(entry.path());
+                if let Ok(path) = entry.path().canonicalize() {
+                    snapshot.index.insert(path, snapshot.files.len());
+                }
+                snapshot.files.push((entry, stamp));
+            }
+        }
+        Ok(snapshot)
+    }
+
+    /// Files that are new or modified since `previous`
+    fn changed(&self, previous: &Self) -> Vec<ignore::DirEntry> {
+        let previous: HashMap<_, _> = previous
+            .files
+            .iter()
+            .map(|(entry, stamp)| (entry.path(), stamp))
+            .collect();
+        self.files
+            .iter()
+            .filter(|(entry, stamp)| previous.get(entry.path()) != Some(&stamp))
+            .map(|(entry, _)| entry.clone())
+            .collect()
+    }
+
+    /// Files among `paths` that were modified, without walking everything again
+    ///
+    /// Paths that weren't found by the last [`Snapshot::scan`], like excluded files, are ignored.
+    fn update(&mut self, paths: &[PathBuf]) -> Vec<ignore::DirEntry> {
+        let mut changed = Vec::new();
+        for path in paths {
+            let Some(i) = path
+                .canonicalize()
+                .ok()
+                .and_then(|path| self.index.get(&path))
+            else {
+                continue;
+            };
+            let (entry, stamp) = &mut self.files[*i];
+            let current = Stamp::new(entry.path());
+            if current != *stamp {
+                *stamp = current;
+                changed.push(entry.clone());
+            }
+        }
+        changed
+    }
+}
+
+/// Whether `kind` only changes the contents of existing files, rather than which files there are
+fn is_content_change(kind: &notify::EventKind) -> bool {
+    matches!(
+        kind,
+        notify::EventKind::Modify(
+            notify::event::ModifyKind::Data(_)
+                | notify::event::ModifyKind::Metadata(_)
+                | notify::event::ModifyKind::Any
+        )
+    )
+}
+
+/// Load the configs for `paths`, including those nested within them
+fn init(
+    paths: &[PathBuf],
+    args: &crate::args::Args,
+    engine: &mut typos_cli::policy::ConfigEngine<'_>,
+) -> Result<(), proc_exit::Exit> {
+    for path in paths {
+        let cwd = crate::path_cwd(path)?;
+        engine
+            .init_dir(&cwd)
+            .with_code(proc_exit::sysexits::CONFIG_ERR)?;
+        crate::init_nested(path, &cwd, args, engine)?;
+    }
+    Ok(())
+}
+
+/// Whether `path` is a config file, including `extend-dict-files`
+fn is_config(path: &Path, engine: &typos_cli::policy::ConfigEngine<'_>) -> bool {
+    let is_config_file = path.file_name().is_some_and(|name| {
+        typos_cli::config::SUPPORTED_FILE_NAMES
+            .iter()
+            .any(|n| *n == name)
+    });
+    is_config_file || engine.is_dict_file(path)
+}
+
+/// Watch `paths` and the directories above them, where their configs may be
+fn watch(
+    paths: &[PathBuf],
+    events: std::sync::mpsc::Sender<notify::Result<notify::Event>>,
+) -> Result<notify::RecommendedWatcher, proc_exit::Exit> {
+    use notify::Watcher as _;
+
+    let mut watcher = notify::recommended_watcher(events).with_code(proc_exit::sysexits::IO_ERR)?;
+    for path in paths {
+        let cwd = crate::path_cwd(path)?;
+        let is_dir = path.is_dir();
+        if is_dir {
+            watcher
+                .watch(&cwd, notify::RecursiveMode::Recursive)
+                .with_code(proc_exit::sysexits::IO_ERR)?;
+        }
+        for dir in cwd.ancestors().skip(usize::from(is_dir)) {
+            if let Err(err) = watcher.watch(dir, notify::RecursiveMode::NonRecursive) {
+                log::debug!("{}: could not watch: {err}", dir.display());
+            }
+        }
+    }
+    Ok(watcher)
+}
+
+/// Check `paths` whenever they change, until interrupted
+///
+/// The initial check is expected to have been done by the caller.
+pub(crate) fn run(
+    paths: &[PathBuf],
+    args: &crate::args::Args,
+    engine: &mut typos_cli::policy::ConfigEngine<'_>,
+    checks: &dyn FileChecker,
+    reporter: &dyn Report,
+    baseline: Option<(&crate::baseline::Baseline, &Path)>,
+) -> Result<(), proc_exit::Exit> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let _watcher = watch(paths, sender)?;
+    let mut snapshot = Snapshot::scan(paths, args, engine)?;
+    let mut failed = false;
+    status("watching for changes")?;
+    while let Ok(event) = receiver.recv() {
+        let mut events = vec![event];
+        while let Ok(event) = receiver.recv_timeout(DEBOUNCE) {
+            events.push(event);
+        }
+        let mut is_config_changed = failed;
+        let mut is_changed = false;
+        let mut rescan = false;
+        let mut modified = Vec::new();
+        for event in events {
+            let event = match event {
+                Ok(event) => event,
+                Err(err) => {
+                    log::debug!("could not watch: {err}");
+                    continue;
+                }
+            };
+            // Checking files reads them
+            if event.kind.is_access() {
+                continue;
+            }
+            is_changed = true;
+            is_config_changed |= event.paths.iter().any(|path| is_config(path, engine));
+            if is_content_change(&event.kind) {
+                modified.extend(event.paths);
+            } else {
+                rescan = true;
+            }
+        }
+        if !is_changed {
+            continue;
+        }
+
+        if is_config_changed {
+            engine.reload();
+            if report_once(init(paths, args, engine), &mut failed).is_none() {
+                continue;
+            }
+        }
+        let changed = if is_config_changed || rescan {
+            let Some(current) = report_once(Snapshot::scan(paths, args, engine), &mut failed)
+            else {
+                continue;
+            };
+            let changed = if is_config_changed {
+                status("config changed, re-checking all files")?;
+                current
+                    .files
+                    .iter()
+                    .map(|(entry, _)| entry.clone())
+                    .collect()
+            } else {
+                current.changed(&snapshot)
+            };
+            snapshot = current;
+            changed
+        } else {
+            snapshot.update(&modified)
+        };
+        failed = false;
+        if changed.is_empty() {
+            continue;
+        }
+
+        // Each batch only sees some of the files, so the baseline starts over
+        let baseline = baseline.map(|(baseline, root)| {
+            crate::baseline::BaselineFilter::new(baseline.clone(), root.to_owned())
+        });
+        let reporter = crate::report::MessageStatus::new(reporter).baseline(baseline.as_ref());
+        typos_cli::file::walk_path(
+            changed.into_iter().map(Ok),
+            checks,
+            engine,
+            &reporter,
+            args.force_exclude,
+        )
+        .map_err(crate::walk_error)?;
+        status("watching for changes")?;
+    }
+    Ok(())
+}
+
+/// Keep watching through config errors, reporting them once, so they can be fixed
+fn report_once<T>(result: Result<T, proc_exit::Exit>, failed: &mut bool) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(err) => {
+            if !*failed {
+                log::error!("{err}");
+                *failed = true;
+            }
+            None
+        }
+    }
+}
+
+fn status(message: &str) -> Result<(), proc_exit::Exit> {
+    let mut stderr = anstream::stderr().lock();
+    let style = anstyle::Style::new().dimmed();
+    writeln!(stderr, "{style}[typos] {message}{style:#}").with_code(proc_exit::sysexits::IO_ERR)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use clap::Parser as _;
+
+    #[test]
+    fn detects_changes() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("a.txt"), "hello").unwrap();
+        std::fs::write(temp.path().join("b.txt"), "world").unwrap();
+        let args = crate::args::Args::parse_from(["typos"]);
+        let paths = [temp.path().to_owned()];
+        let storage = typos_cli::policy::ConfigStorage::new();
+        let mut engine = typos_cli::policy::ConfigEngine::new(&storage);
+        init(&paths, &args, &mut engine).unwrap();
+
+        let scan = |engine: &_| Snapshot::scan(&paths, &args, engine).unwrap();
+        let changed = |current: &Snapshot, previous: &Snapshot| {
+            let mut changed: Vec<_> = current
+                .changed(previous)
+                .iter()
+                .map(|entry| entry.file_name().to_string_lossy().into_owned())
+                .collect();
+            changed.sort();
+            changed
+        };
+
+        let previous = scan(&engine);
+        let current = scan(&engine);
+        assert!(changed(&current, &previous).is_empty());
+
+        std::fs::write(temp.path().join("b.txt"), "world!").unwrap();
+        std::fs::write(temp.path().join("c.txt"), "new").unwrap();
+        let previous = current;
+        let mut current = scan(&engine);
+        assert_eq!(changed(&current, &previous), ["b.txt", "c.txt"]);
+
+        std::fs::write(temp.path().join("a.txt"), "hello!").unwrap();
+        std::fs::write(temp.path().join("d.txt"), "unscanned").unwrap();
+        let updated: Vec<_> = current
+            .update(&[temp.path().join("a.txt"), temp.path().join("d.txt")])
+            .iter()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(updated, ["a.txt"]);
+        assert!(current.update(&[temp.path().join("a.txt")]).is_empty());
+
+        assert!(!is_config(&temp.path().join("c.txt"), &engine));
+        assert!(is_config(&temp.path().join("_typos.toml"), &engine));
+    }
+}
@@ -0,0 +1,341 @@
+//! Check and fix files or in-memory buffers, for embedding `typos` in other tools
+//!
+//! Unlike [`crate::file::FileChecker`], results are returned as owned [`Finding`]s rather than
+//! going through a [`crate::report::Report`].
+//!
+//! ```rust
+//! # fn main() -> Result<(), anyhow::Error> {
+//! use typos_cli::checker::{Checker, ColumnUnit, Config, ConfigStorage};
+//!
+//! let storage = ConfigStorage::new();
+//! let config = Config::from_toml("[default.extend-words]\nfoo = \"foo\"")?;
+//! let checker = Checker::with_config(&storage, "/project".as_ref(), config)?;
+//!
+//! let findings = checker.check_buffer("src/lib.rs".as_ref(), b"// Fix teh foo\n");
+//! assert_eq!(findings.len(), 1);
+//! assert_eq!(findings[0].typo, "teh");
+//! assert_eq!(findings[0].line_num, Some(1));
+//! assert_eq!(findings[0].columns(ColumnUnit::Char), 8..11);
+//!
+//! let fixed = checker.fix_buffer("src/lib.rs".as_ref(), b"// Fix teh foo\n");
+//! assert_eq!(fixed, b"// Fix the foo\n");
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::{Path, PathBuf};
+
+use crate::file::{AccumulateLineNum, check_bytes, check_str, extract_line, generated_reason};
+
+pub use crate::config::Config;
+pub use crate::policy::ConfigStorage;
+pub use crate::report::ColumnUnit;
+
+/// Check paths and buffers against the config for a project
+pub struct Checker<'s> {
+    engine: crate::policy::ConfigEngine<'s>,
+    root: PathBuf,
+}
+
+impl<'s> Checker<'s> {
+    /// Use the config files found in `root`, its ancestors, and its subdirectories, like `typos`
+    /// does
+    pub fn new(storage: &'s ConfigStorage, root: &Path) -> Result<Self, anyhow::Error> {
+        let root = root.canonicalize()?;
+        let mut engine = crate::policy::ConfigEngine::new(storage);
+        engine.init_dir(&root)?;
+        let walk = nested_walk(&root, engine.walk(&root))?;
+        engine.init_nested(walk)?;
+        Ok(Self { engine, root })
+    }
+
+    /// Only use `config`, without looking for config files
+    ///
+    /// `root` is only used to resolve relative paths, and does not need to exist.
+    pub fn with_config(
+        storage: &'s ConfigStorage,
+        root: &Path,
+        config: Config,
+    ) -> Result<Self, anyhow::Error> {
+        let root = std::path::absolute(root)?;
+        let mut engine = crate::policy::ConfigEngine::new(storage);
+        engine.set_isolated(true).set_overrides(config);
+        engine.init_dir(&root)?;
+        Ok(Self { engine, root })
+    }
+
+    /// The settings for `path`, relative to the root
+    ///
+    /// Paths outside of the root get the settings for a file of the same name in the root.
+    fn policy(&self, path: &Path) -> crate::policy::Policy<'_, '_, '_> {
+        let mut abs_path = normalize(&self.root.join(path));
+        if !abs_path.starts_with(&self.root) {
+            abs_path = self.root.join(path.file_name().unwrap_or_default());
+        }
+        self.engine.policy(&abs_path)
+    }
+
+    /// Read and check the file at `path`, including its name
+    ///
+    /// Binary files only have their name checked, unless `binary = true`, as do generated files
+    /// with `skip-generated = true` and files over `max-file-size`.
+    /// Lines over `max-line-length` are skipped.
+    pub fn check_path(&self, path: &Path) -> Result<Vec<Finding>, std::io::Error> {
+        let policy = self.policy(path);
+        let mut findings = Vec::new();
+
+        if policy.check_filenames {
+            if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
+                let mut ignores = None;
+                for typo in check_str(file_name, &policy, &mut ignores, false) {
+                    findings.push(Finding {
+                        path: path.to_owned(),
+                        line_num: None,
+                        offset: None,
+                        line: file_name.as_bytes().to_vec(),
+                        line_offset: typo.byte_offset,
+                        preferred: crate::file::is_preferred(&policy, &typo),
+                        typo: typo.typo.into_owned(),
+                        corrections: typo.corrections.into_owned(),
+                    });
+                }
+            }
+        }
+
+        let abs_path = self.root.join(path);
+        let too_large = policy.max_file_size.is_some_and(|max_file_size| {
+            std::fs::metadata(&abs_path).is_ok_and(|metadata| max_file_size < metadata.len())
+        });
+        if policy.check_files && !too_large {
+            let (buffer, content_type) = crate::file::read_file(&abs_path, &ErrorToResult)?;
+            let generated = policy.skip_generated
+                && policy
+                    .generated_attribute
+                    .or_else(|| generated_reason(path, &buffer, policy.file_type))
+                    .is_some();
+            if (policy.binary || !content_type.is_binary()) && !generated {
+                findings.extend(buffer_findings(path, &buffer, &policy));
+            }
+        }
+
+        Ok(findings)
+    }
+
+    /// Check the contents of `buffer` as if they were the file at `path`
+    ///
+    /// `path` selects the settings, like for the file type, and is not read.
+    pub fn check_buffer(&self, path: &Path, buffer: &[u8]) -> Vec<Finding> {
+        let policy = self.policy(path);
+        if !policy.check_files {
+            return Vec::new();
+        }
+        buffer_findings(path, buffer, &policy)
+    }
+
+    /// Apply the corrections for typos with only one, as if `buffer` were the file at `path`
+    ///
+    /// Typos that remain can be found by checking the result.
+    pub fn fix_buffer(&self, path: &Path, buffer: &[u8]) -> Vec<u8> {
+        let policy = self.policy(path);
+        if !policy.check_files {
+            return buffer.to_vec();
+        }
+        let mut ignores = None;
+        let fixes: Vec<_> = check_bytes(buffer, &policy, &mut ignores, true)
+            .filter(crate::file::is_fixable)
+            .map(|typo| typo.into_owned())
+            .collect();
+        crate::file::fix_buffer(buffer.to_vec(), fixes.into_iter())
+    }
+}
+
+/// Walk `root` like `typos` does, for finding nested configs
+fn nested_walk(
+    root: &Path,
+    walk_policy: &crate::config::Walk,
+) -> Result<ignore::Walk, anyhow::Error> {
+    let mut excludes = ignore::gitignore::GitignoreBuilder::new(root);
+    for pattern in walk_policy.extend_exclude() {
+        excludes.add_line(None, pattern)?;
+    }
+    let excludes = excludes.build()?;
+    let walk = ignore::WalkBuilder::new(root)
+        .hidden(walk_policy.ignore_hidden())
+        .ignore(walk_policy.ignore_dot())
+        .git_global(walk_policy.ignore_global())
+        .git_ignore(walk_policy.ignore_vcs())
+        .git_exclude(walk_policy.ignore_vcs())
+        .parents(walk_policy.ignore_parent())
+        .filter_entry(move |entry| {
+            let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+            !excludes.matched(entry.path(), is_dir).is_ignore()
+        })
+        .build();
+    Ok(walk)
+}
+
+/// Resolve `..` and `.` without touching the filesystem, so they can't escape the root
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+fn buffer_findings(
+    path: &Path,
+    buffer: &[u8],
+    policy: &crate::policy::Policy<'_, '_, '_>,
+) -> Vec<Finding> {
+    let mut accum_line_num = AccumulateLineNum::new();
+    let mut ignores = None;
+    check_bytes(buffer, policy, &mut ignores, false)
+        .map(|typo| {
+            let line_num = accum_line_num.line_num(buffer, typo.byte_offset);
+            let (line, line_offset) = extract_line(buffer, typo.byte_offset);
+            Finding {
+                path: path.to_owned(),
+                line_num: Some(line_num),
+                offset: Some(typo.byte_offset),
+                line: line.to_vec(),
+                line_offset,
+                preferred: crate::file::is_preferred(policy, &typo),
+                typo: typo.typo.into_owned(),
+                corrections: typo.corrections.into_owned(),
+            }
+        })
+        .collect()
+}
+
+/// A typo found by a [`Checker`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Finding {
+    pub path: PathBuf,
+    /// 1-based line number, `None` for typos in the file name
+    pub line_num: Option<usize>,
+    /// Byte offset within the buffer, `None` for typos in the file name
+    pub offset: Option<usize>,
+    /// The line with the typo, or the file name
+    pub line: Vec<u8>,
+    /// Byte offset within `line`
+    pub line_offset: usize,
+    pub typo: String,
+    pub corrections: typos::Status<'static>,
+    /// Whether `corrections` was narrowed down by `preferred-corrections`
+    pub preferred: bool,
+}
+
+impl Finding {
+    /// 1-based, end-exclusive columns of the typo within `line`
+    pub fn columns(&self, unit: ColumnUnit) -> std::ops::Range<usize> {
+        self.to_typo().columns(unit)
+    }
+
+    /// View as a message, for passing to a [`crate::report::Report`]
+    pub fn to_typo(&self) -> crate::report::Typo<'_> {
+        let context = match self.line_num {
+            Some(line_num) => crate::report::FileContext {
+                path: &self.path,
+                line_num,
+                line_start: self.offset.unwrap_or_default() - self.line_offset,
+            }
+            .into(),
+            None => crate::report::PathContext { path: &self.path }.into(),
+        };
+        crate::report::Typo {
+            context: Some(context),
+            buffer: std::borrow::Cow::Borrowed(&self.line),
+            byte_offset: self.line_offset,
+            typo: &self.typo,
+            corrections: self.corrections.borrow(),
+            preferred: self.preferred,
+        }
+    }
+}
+
+/// Turn errors reading a file into the `Result` of reporting them
+struct ErrorToResult;
+
+impl crate::report::Report for ErrorToResult {
+    fn report(&self, msg: crate::report::Message<'_>) -> Result<(), std::io::Error> {
+        match msg {
+            crate::report::Message::Error(err) => Err(std::io::Error::other(err.msg)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checker<'s>(storage: &'s ConfigStorage, config: &str) -> Checker<'s> {
+        let config = Config::from_toml(config).unwrap();
+        Checker::with_config(storage, Path::new("/project"), config).unwrap()
+    }
+
+    #[test]
+    fn test_check_buffer_positions() {
+        let storage = ConfigStorage::new();
+        let checker = checker(&storage, "");
+        let findings =
+            checker.check_buffer(Path::new("notes.txt"), "first\nnaïve teh\n".as_bytes());
+        assert_eq!(findings.len(), 1);
+        let finding = &findings[0];
+        assert_eq!(finding.typo, "teh");
+        assert_eq!(finding.line_num, Some(2));
+        assert_eq!(finding.offset, Some(13));
+        assert_eq!(finding.line, "naïve teh".as_bytes());
+        assert_eq!(finding.columns(ColumnUnit::Byte), 8..11);
+        assert_eq!(finding.columns(ColumnUnit::Char), 7..10);
+        assert_eq!(
+            finding.corrections,
+            typos::Status::Corrections(vec!["the".into()])
+        );
+    }
+
+    #[test]
+    fn test_check_buffer_uses_file_type() {
+        let storage = ConfigStorage::new();
+        let checker = checker(&storage, "[type.md]\ncheck-file = false\n");
+        assert!(
+            checker
+                .check_buffer(Path::new("README.md"), b"teh")
+                .is_empty()
+        );
+        assert_eq!(
+            checker.check_buffer(Path::new("README.txt"), b"teh").len(),
+            1
+        );
+        // Outside of the root, only the name matters
+        assert!(
+            checker
+                .check_buffer(Path::new("/other/README.md"), b"teh")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(Path::new("/project/./src/../../other/README.md")),
+            Path::new("/other/README.md")
+        );
+    }
+
+    #[test]
+    fn test_fix_buffer() {
+        let storage = ConfigStorage::new();
+        let checker = checker(&storage, "[default.extend-words]\nabck = \"abck\"\n");
+        let fixed = checker.fix_buffer(Path::new("notes.txt"), b"teh abck\nteh ABCD\n");
+        assert_eq!(fixed, b"the abck\nthe ABCD\n");
+    }
+}
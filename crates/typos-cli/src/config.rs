@@ -1,5 +1,6 @@
 #![allow(unused_qualifications)] // schemars
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 
 use kstring::KString;
@@ -23,12 +24,23 @@ const PYPROJECT_TOML: &str = "pyproject.toml";
 #[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
 pub struct Config {
+    /// Don't merge in the config from the parent directory, for nested configs.
+    pub root: Option<bool>,
+    /// Configs to merge in before this one, relative to this one.
+    #[serde(skip_serializing)]
+    pub extend_config: Vec<std::path::PathBuf>,
     pub files: Walk,
     pub default: EngineConfig,
     #[serde(rename = "type")]
     pub type_: TypeEngineConfig,
+    /// Settings for files matching `globs`, applied in order on top of `default` and `type`.
+    #[serde(rename = "overrides")]
+    pub glob_overrides: Vec<GlobOverrideConfig>,
     #[serde(skip)]
     pub overrides: EngineConfig,
+    /// Where values came from, when loaded with [`Loader::track_provenance`]
+    #[serde(skip)]
+    pub provenance: Provenance,
 }
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -69,9 +81,136 @@ pub struct PyprojectTomlTool {
 
 impl Config {
     pub fn from_dir(cwd: &std::path::Path) -> Result<Option<Self>, anyhow::Error> {
+        Loader::new().load_dir(cwd)
+    }
+
+    pub fn from_file(path: &std::path::Path) -> Result<Option<Self>, anyhow::Error> {
+        Loader::new().load_file(path)
+    }
+
+    pub fn from_toml(data: &str) -> Result<Self, anyhow::Error> {
+        let content = toml::from_str(data)?;
+        Ok(content)
+    }
+
+    pub fn from_defaults() -> Self {
+        Self {
+            root: None,
+            extend_config: Default::default(),
+            files: Walk::from_defaults(),
+            default: EngineConfig::from_defaults(),
+            type_: TypeEngineConfig::from_defaults(),
+            glob_overrides: Default::default(),
+            overrides: EngineConfig::default(),
+            provenance: Provenance::default(),
+        }
+    }
+
+    pub fn update(&mut self, source: &Config) {
+        self.track(&source.provenance, |config| {
+            if let Some(source) = source.root {
+                config.root = Some(source);
+            }
+            config.files.update(&source.files);
+            config.default.update(&source.default);
+            config.type_.update(&source.type_);
+            config
+                .glob_overrides
+                .extend(source.glob_overrides.iter().cloned());
+            config.overrides.update(&source.overrides);
+        });
+    }
+
+    /// Apply `f`, forgetting where the values it changes came from
+    pub fn modify(&mut self, f: impl FnOnce(&mut Self)) {
+        self.track(&Provenance::default(), f);
+    }
+
+    /// Apply `f`, attributing the values it changes to `source`
+    fn track(&mut self, source: &Provenance, f: impl FnOnce(&mut Self)) {
+        if self.provenance.sources.is_empty() && source.sources.is_empty() {
+            f(self);
+            return;
+        }
+        let before = Provenance::values(self);
+        f(self);
+        let after = Provenance::values(self);
+        let mut provenance = std::mem::take(&mut self.provenance);
+        provenance.record(&before, &after, source);
+        self.provenance = provenance;
+    }
+
+    pub fn root(&self) -> bool {
+        self.root.unwrap_or(false)
+    }
+
+    /// Make paths relative to `base`, the directory containing the config file
+    fn resolve_paths(&mut self, base: &std::path::Path) {
+        for path in self.extend_config.iter_mut() {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        }
+        self.default.dict.resolve_paths(base);
+        for glob in self.type_.patterns.values_mut() {
+            glob.engine.dict.resolve_paths(base);
+        }
+        for glob_override in self.glob_overrides.iter_mut() {
+            glob_override.engine.dict.resolve_paths(base);
+            glob_override.base = Some(base.to_owned());
+        }
+        self.overrides.dict.resolve_paths(base);
+    }
+
+    fn unused(&self) -> impl Iterator<Item = String> + '_ {
+        self.default
+            ._unused
+            .keys()
+            .map(|k| format!("default.{k}"))
+            .chain(self.type_.patterns.iter().flat_map(|(name, glob)| {
+                glob.engine._unused.keys().map(|k| {
+                    let name = name.clone();
+                    format!("type.{name}.{k}")
+                })
+            }))
+            .chain(
+                self.glob_overrides
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, glob_override)| {
+                        glob_override
+                            .engine
+                            ._unused
+                            .keys()
+                            .map(move |k| format!("overrides[{i}].{k}"))
+                    }),
+            )
+    }
+}
+
+/// Reads config files
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Loader {
+    track_provenance: bool,
+}
+
+impl Loader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the files each value came from in [`Config::provenance`]
+    ///
+    /// This is only needed to report where values came from, like for `--dump-config`.
+    pub fn track_provenance(mut self, yes: bool) -> Self {
+        self.track_provenance = yes;
+        self
+    }
+
+    pub fn load_dir(&self, cwd: &std::path::Path) -> Result<Option<Config>, anyhow::Error> {
         for file in find_project_files(cwd, SUPPORTED_FILE_NAMES) {
             log::debug!("Loading {}", file.display());
-            if let Some(config) = Self::from_file(&file)? {
+            if let Some(config) = self.load_file(&file)? {
                 return Ok(Some(config));
             }
         }
@@ -79,7 +218,18 @@ impl Config {
         Ok(None)
     }
 
-    pub fn from_file(path: &std::path::Path) -> Result<Option<Self>, anyhow::Error> {
+    pub fn load_file(&self, path: &std::path::Path) -> Result<Option<Config>, anyhow::Error> {
+        self.load_file_included(path, &mut Vec::new())
+    }
+
+    /// Load `path`, merged on top of its `extend-config`s
+    ///
+    /// `includes` are the configs that led to `path` being loaded, to detect cycles.
+    fn load_file_included(
+        &self,
+        path: &std::path::Path,
+        includes: &mut Vec<std::path::PathBuf>,
+    ) -> Result<Option<Config>, anyhow::Error> {
         let s = std::fs::read_to_string(path).map_err(|err| {
             let kind = err.kind();
             std::io::Error::new(
@@ -88,7 +238,7 @@ impl Config {
             )
         })?;
 
-        let config = if path.file_name().unwrap() == CARGO_TOML {
+        let mut config = if path.file_name().unwrap() == CARGO_TOML {
             let config = toml::from_str::<CargoTomlConfig>(&s)?;
             let typos = config
                 .workspace
@@ -115,47 +265,134 @@ impl Config {
                 return Ok(None);
             }
         } else {
-            Self::from_toml(&s)?
+            Config::from_toml(&s)?
         };
         if let Some(key) = config.unused().next() {
             anyhow::bail!("unknown key `{key}`");
         }
+        if let Some(base) = path.parent() {
+            config.resolve_paths(base);
+        }
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+        if self.track_provenance {
+            config.provenance = Provenance::from_config(&config, &path);
+        }
+        if config.extend_config.is_empty() {
+            return Ok(Some(config));
+        }
+
+        includes.push(path.clone());
+        let mut merged = Config::default();
+        for include in std::mem::take(&mut config.extend_config) {
+            let include = include.canonicalize().unwrap_or(include);
+            let included = if includes.contains(&include) {
+                Err(anyhow::format_err!(
+                    "`{}` includes itself through `extend-config`",
+                    include.display()
+                ))
+            } else {
+                self.load_file_included(&include, includes)
+            };
+            let included = included.map_err(|err| {
+                anyhow::format_err!("{err}\n  included from `{}`", path.display())
+            })?;
+            if let Some(included) = included {
+                merged.update(&included);
+            }
+        }
+        includes.pop();
+        merged.update(&config);
 
-        Ok(Some(config))
+        Ok(Some(merged))
     }
+}
 
-    pub fn from_toml(data: &str) -> Result<Self, anyhow::Error> {
-        let content = toml::from_str(data)?;
-        Ok(content)
+/// The config files that each value of a [`Config`] came from
+///
+/// Values are identified by their path of keys, like `["default", "extend-words", "teh"]`.
+#[derive(Debug, Clone, Default)]
+pub struct Provenance {
+    sources: BTreeMap<Vec<String>, Vec<std::path::PathBuf>>,
+}
+
+impl Provenance {
+    fn from_config(config: &Config, path: &std::path::Path) -> Self {
+        let sources = Self::values(config)
+            .into_keys()
+            .map(|key| (key, vec![path.to_owned()]))
+            .collect();
+        Self { sources }
     }
 
-    pub fn from_defaults() -> Self {
-        Self {
-            files: Walk::from_defaults(),
-            default: EngineConfig::from_defaults(),
-            type_: TypeEngineConfig::from_defaults(),
-            overrides: EngineConfig::default(),
-        }
+    /// The config files that set `key`, in the order they were merged
+    pub fn get(&self, key: &[String]) -> &[std::path::PathBuf] {
+        self.sources.get(key).map(Vec::as_slice).unwrap_or_default()
     }
 
-    pub fn update(&mut self, source: &Config) {
-        self.files.update(&source.files);
-        self.default.update(&source.default);
-        self.type_.update(&source.type_);
-        self.overrides.update(&source.overrides);
+    fn values(config: &Config) -> BTreeMap<Vec<String>, serde_json::Value> {
+        fn flatten(
+            key: &mut Vec<String>,
+            value: serde_json::Value,
+            values: &mut BTreeMap<Vec<String>, serde_json::Value>,
+        ) {
+            match value {
+                serde_json::Value::Object(map) => {
+                    for (name, value) in map {
+                        key.push(name);
+                        flatten(key, value, values);
+                        key.pop();
+                    }
+                }
+                serde_json::Value::Null => {}
+                value => {
+                    values.insert(key.clone(), value);
+                }
+            }
+        }
+
+        let mut values = BTreeMap::new();
+        let value = serde_json::to_value(config).expect("configs are always serializable");
+        flatten(&mut Vec::new(), value, &mut values);
+        values
     }
 
-    fn unused(&self) -> impl Iterator<Item = String> + '_ {
-        self.default
-            ._unused
-            .keys()
-            .map(|k| format!("default.{k}"))
-            .chain(self.type_.patterns.iter().flat_map(|(name, glob)| {
-                glob.engine._unused.keys().map(|k| {
-                    let name = name.clone();
-                    format!("type.{name}.{k}")
-                })
-            }))
+    /// Attribute the values changed from `before` to `after` to `source`
+    ///
+    /// Lists that were extended keep their earlier sources.
+    fn record(
+        &mut self,
+        before: &BTreeMap<Vec<String>, serde_json::Value>,
+        after: &BTreeMap<Vec<String>, serde_json::Value>,
+        source: &Provenance,
+    ) {
+        self.sources.retain(|key, _| after.contains_key(key));
+        for (key, value) in after {
+            let sources = source.get(key);
+            match (before.get(key), value) {
+                (Some(old), _) if old == value => {
+                    // Explicitly set to what it already was
+                    if !sources.is_empty() && !value.is_array() {
+                        self.sources.insert(key.clone(), sources.to_vec());
+                    }
+                }
+                (Some(serde_json::Value::Array(old)), serde_json::Value::Array(new))
+                    if new.starts_with(old) =>
+                {
+                    let merged = self.sources.entry(key.clone()).or_default();
+                    for path in sources {
+                        if !merged.contains(path) {
+                            merged.push(path.clone());
+                        }
+                    }
+                }
+                _ if sources.is_empty() => {
+                    self.sources.remove(key);
+                }
+                _ => {
+                    self.sources.insert(key.clone(), sources.to_vec());
+                }
+            }
+        }
     }
 }
 
@@ -343,6 +580,22 @@ impl GlobEngineConfig {
     }
 }
 
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+//#[serde(deny_unknown_fields)]  // Doesn't work with `flatten`
+#[serde(default)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+pub struct GlobOverrideConfig {
+    /// Paths to apply these settings to, as `.gitignore` patterns relative to the config file.
+    #[cfg_attr(feature = "unstable-schema", schemars(schema_with = "vec_string"))]
+    pub globs: Vec<KString>,
+    #[serde(flatten)]
+    pub engine: EngineConfig,
+    /// The directory `globs` are relative to, when not the one being checked
+    #[serde(skip)]
+    pub base: Option<std::path::PathBuf>,
+}
+
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 //#[serde(deny_unknown_fields)]  // Doesn't work with `flatten`
 #[serde(default)]
@@ -355,6 +608,18 @@ pub struct EngineConfig {
     pub check_filename: Option<bool>,
     /// Verifying spelling in files.
     pub check_file: Option<bool>,
+    /// Parts of files to check, for file types whose syntax is understood.
+    pub check: Option<Vec<Region>>,
+    /// Skip code spans, link targets, and HTML tags in markup (`md`, `rst`).
+    pub markup: Option<bool>,
+    /// How to check code blocks in markup (`md`, `rst`).
+    pub code_blocks: Option<CodeBlocks>,
+    /// Skip generated and vendored files, as marked in their contents or `.gitattributes`.
+    pub skip_generated: Option<bool>,
+    /// Skip files larger than this many bytes.
+    pub max_file_size: Option<u64>,
+    /// Skip lines longer than this many bytes.
+    pub max_line_length: Option<usize>,
     #[serde(flatten)]
     pub tokenizer: TokenizerConfig,
     #[serde(flatten)]
@@ -374,6 +639,12 @@ impl EngineConfig {
             binary: Some(empty.binary()),
             check_filename: Some(empty.check_filename()),
             check_file: Some(empty.check_file()),
+            check: Some(empty.check().to_vec()),
+            markup: Some(empty.markup()),
+            code_blocks: Some(empty.code_blocks()),
+            skip_generated: Some(empty.skip_generated()),
+            max_file_size: empty.max_file_size(),
+            max_line_length: empty.max_line_length(),
             tokenizer: TokenizerConfig::from_defaults(),
             dict: DictConfig::from_defaults(),
             extend_ignore_re: Default::default(),
@@ -391,6 +662,24 @@ impl EngineConfig {
         if let Some(source) = source.check_file {
             self.check_file = Some(source);
         }
+        if let Some(source) = source.check.as_ref() {
+            self.check = Some(source.clone());
+        }
+        if let Some(source) = source.markup {
+            self.markup = Some(source);
+        }
+        if let Some(source) = source.code_blocks {
+            self.code_blocks = Some(source);
+        }
+        if let Some(source) = source.skip_generated {
+            self.skip_generated = Some(source);
+        }
+        if let Some(source) = source.max_file_size {
+            self.max_file_size = Some(source);
+        }
+        if let Some(source) = source.max_line_length {
+            self.max_line_length = Some(source);
+        }
         self.tokenizer.update(&source.tokenizer);
         self.dict.update(&source.dict);
         self.extend_ignore_re
@@ -410,6 +699,32 @@ impl EngineConfig {
         self.check_file.unwrap_or(true)
     }
 
+    pub fn check(&self) -> &[Region] {
+        self.check.as_deref().unwrap_or(Region::ALL)
+    }
+
+    pub fn markup(&self) -> bool {
+        self.markup.unwrap_or(false)
+    }
+
+    pub fn code_blocks(&self) -> CodeBlocks {
+        self.code_blocks.unwrap_or_default()
+    }
+
+    pub fn skip_generated(&self) -> bool {
+        self.skip_generated.unwrap_or(false)
+    }
+
+    /// `None` when there is no limit
+    pub fn max_file_size(&self) -> Option<u64> {
+        self.max_file_size
+    }
+
+    /// `None` when there is no limit
+    pub fn max_line_length(&self) -> Option<usize> {
+        self.max_line_length
+    }
+
     pub fn extend_ignore_re(&self) -> Box<dyn Iterator<Item = &regex::Regex> + '_> {
         Box::new(self.extend_ignore_re.iter())
     }
@@ -471,6 +786,8 @@ impl TokenizerConfig {
 #[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
 pub struct DictConfig {
     pub locale: Option<Locale>,
+    /// Built-in dictionaries to use, in order of precedence
+    pub dictionaries: Option<Vec<Dictionary>>,
     #[serde(with = "serde_regex")]
     #[cfg_attr(feature = "unstable-schema", schemars(schema_with = "vec_string"))]
     pub extend_ignore_identifiers_re: Vec<regex::Regex>,
@@ -487,6 +804,16 @@ pub struct DictConfig {
         schemars(schema_with = "hashmap_string_string")
     )]
     pub extend_words: HashMap<KString, KString>,
+    /// Word lists and `typo,correction...` CSV (`.csv`) files, relative to the config file
+    pub extend_dict_files: Vec<std::path::PathBuf>,
+    /// The correction to use for typos with several, like when fixing
+    #[cfg_attr(
+        feature = "unstable-schema",
+        schemars(schema_with = "hashmap_string_string")
+    )]
+    pub preferred_corrections: HashMap<KString, KString>,
+    /// Order corrections by how likely they are meant, fixing typos when one is clearly best
+    pub rank_corrections: Option<bool>,
 }
 
 impl DictConfig {
@@ -494,10 +821,14 @@ impl DictConfig {
         let empty = Self::default();
         Self {
             locale: Some(empty.locale()),
+            dictionaries: Some(empty.dictionaries().to_vec()),
             extend_ignore_identifiers_re: Default::default(),
             extend_identifiers: Default::default(),
             extend_ignore_words_re: Default::default(),
             extend_words: Default::default(),
+            extend_dict_files: Default::default(),
+            preferred_corrections: Default::default(),
+            rank_corrections: Some(empty.rank_corrections()),
         }
     }
 
@@ -505,6 +836,9 @@ impl DictConfig {
         if let Some(source) = source.locale {
             self.locale = Some(source);
         }
+        if let Some(source) = source.dictionaries.as_ref() {
+            self.dictionaries = Some(source.clone());
+        }
         self.extend_ignore_identifiers_re
             .extend(source.extend_ignore_identifiers_re.iter().cloned());
         self.extend_identifiers.extend(
@@ -521,12 +855,27 @@ impl DictConfig {
                 .iter()
                 .map(|(key, value)| (key.clone(), value.clone())),
         );
+        self.extend_dict_files
+            .extend(source.extend_dict_files.iter().cloned());
+        if let Some(source) = source.rank_corrections {
+            self.rank_corrections = Some(source);
+        }
+        self.preferred_corrections.extend(
+            source
+                .preferred_corrections
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
     }
 
     pub fn locale(&self) -> Locale {
         self.locale.unwrap_or_default()
     }
 
+    pub fn dictionaries(&self) -> &[Dictionary] {
+        self.dictionaries.as_deref().unwrap_or(&[Dictionary::Typos])
+    }
+
     pub fn extend_ignore_identifiers_re(&self) -> Box<dyn Iterator<Item = &regex::Regex> + '_> {
         Box::new(self.extend_ignore_identifiers_re.iter())
     }
@@ -550,6 +899,30 @@ impl DictConfig {
                 .map(|(k, v)| (k.as_str(), v.as_str())),
         )
     }
+
+    pub fn rank_corrections(&self) -> bool {
+        self.rank_corrections.unwrap_or(false)
+    }
+
+    pub fn preferred_corrections(&self) -> Box<dyn Iterator<Item = (&str, &str)> + '_> {
+        Box::new(
+            self.preferred_corrections
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str())),
+        )
+    }
+
+    pub fn extend_dict_files(&self) -> &[std::path::PathBuf] {
+        &self.extend_dict_files
+    }
+
+    fn resolve_paths(&mut self, base: &std::path::Path) {
+        for path in self.extend_dict_files.iter_mut() {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        }
+    }
 }
 
 fn find_project_files<'a>(
@@ -562,6 +935,81 @@ fn find_project_files<'a>(
         .filter(|path| path.exists())
 }
 
+/// Part of a file, as understood by a lightweight lexer for its file type
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+pub enum Region {
+    Comments,
+    Strings,
+    /// Everything outside of comments and strings
+    Identifiers,
+}
+
+impl Region {
+    pub const ALL: &'static [Self] = &[Self::Comments, Self::Strings, Self::Identifiers];
+}
+
+/// Built-in word dictionary
+#[derive(Debug, Copy, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+pub enum Dictionary {
+    Typos,
+    /// Requires the `codespell` feature
+    Codespell,
+    /// Requires the `misspell` feature
+    Misspell,
+    /// Requires the `wikipedia` feature
+    Wikipedia,
+}
+
+impl Dictionary {
+    /// Whether the dictionary was compiled in
+    pub const fn is_available(self) -> bool {
+        match self {
+            Dictionary::Typos => cfg!(feature = "dict"),
+            Dictionary::Codespell => cfg!(feature = "codespell"),
+            Dictionary::Misspell => cfg!(feature = "misspell"),
+            Dictionary::Wikipedia => cfg!(feature = "wikipedia"),
+        }
+    }
+
+    pub const fn feature(self) -> &'static str {
+        match self {
+            Dictionary::Typos => "dict",
+            Dictionary::Codespell => "codespell",
+            Dictionary::Misspell => "misspell",
+            Dictionary::Wikipedia => "wikipedia",
+        }
+    }
+}
+
+impl std::fmt::Display for Dictionary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Dictionary::Typos => write!(f, "typos"),
+            Dictionary::Codespell => write!(f, "codespell"),
+            Dictionary::Misspell => write!(f, "misspell"),
+            Dictionary::Wikipedia => write!(f, "wikipedia"),
+        }
+    }
+}
+
+/// How to check code blocks in markup
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+pub enum CodeBlocks {
+    /// Check code blocks like the surrounding prose
+    #[default]
+    Check,
+    /// Don't check code blocks
+    Skip,
+    /// Only check comments and strings, using the code block's language
+    Language,
+}
+
 #[derive(Debug, Copy, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
 #[derive(Default)]
@@ -729,6 +1177,50 @@ mod test {
         assert_data_eq!(actual.extend_glob.into_json(), expected.into_json());
     }
 
+    #[test]
+    fn test_update_tracks_provenance() {
+        let base_path = std::path::Path::new("shared/base.toml");
+        let mut base = Config::from_toml(
+            r#"[files]
+extend-exclude = ["vendor"]
+
+[default.extend-words]
+teh = "teh"
+"#,
+        )
+        .unwrap();
+        base.provenance = Provenance::from_config(&base, base_path);
+        let local_path = std::path::Path::new("_typos.toml");
+        let mut local = Config::from_toml(
+            r#"[files]
+extend-exclude = ["target"]
+
+[default]
+binary = true
+"#,
+        )
+        .unwrap();
+        local.provenance = Provenance::from_config(&local, local_path);
+
+        let mut merged = Config::from_defaults();
+        merged.update(&base);
+        merged.update(&local);
+        let key = |key: &str| key.split('.').map(String::from).collect::<Vec<_>>();
+        assert_eq!(
+            merged.provenance.get(&key("files.extend-exclude")),
+            [base_path, local_path]
+        );
+        assert_eq!(
+            merged.provenance.get(&key("default.extend-words.teh")),
+            [base_path]
+        );
+        assert_eq!(merged.provenance.get(&key("default.binary")), [local_path]);
+        assert!(merged.provenance.get(&key("default.locale")).is_empty());
+
+        merged.modify(|config| config.default.binary = Some(false));
+        assert!(merged.provenance.get(&key("default.binary")).is_empty());
+    }
+
     #[test]
     fn parse_extend_globs() {
         let input = r#"[type.po]
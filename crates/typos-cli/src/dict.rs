@@ -6,18 +6,28 @@ use unicase::UniCase;
 use typos::Status;
 use typos::tokens::Case;
 
-#[derive(Default)]
 pub struct BuiltIn {
     locale: Option<varcon_core::Category>,
+    dictionaries: Cow<'static, [crate::config::Dictionary]>,
 }
 
 impl BuiltIn {
     pub const fn new(locale: crate::config::Locale) -> Self {
         Self {
             locale: locale.category(),
+            dictionaries: Cow::Borrowed(&[crate::config::Dictionary::Typos]),
         }
     }
 
+    /// Word dictionaries to use, in order of precedence
+    ///
+    /// Corrections are merged across dictionaries.
+    /// A word is only invalid (no corrections) if the first dictionary with it says so.
+    pub fn with_dictionaries(mut self, dictionaries: Vec<crate::config::Dictionary>) -> Self {
+        self.dictionaries = Cow::Owned(dictionaries);
+        self
+    }
+
     pub fn correct_ident<'s>(
         &'s self,
         ident_token: typos::tokens::Identifier<'_>,
@@ -33,11 +43,11 @@ impl BuiltIn {
 
         let word = word_token.token();
         let word_case = UniCase::new(word);
-        let mut corrections = if let Some(corrections) = self.correct_word_with_dict(word_case) {
+        let mut corrections = if let Some(corrections) = self.correct_word_with_dicts(word_case) {
             if corrections.is_empty() {
                 Status::Invalid
             } else {
-                self.chain_with_vars(corrections)
+                self.chain_with_vars(&corrections)
             }
         } else {
             self.correct_with_vars(word_case)?
@@ -58,11 +68,6 @@ impl BuiltIn {
             _ => None,
         }
     }
-
-    // Not using `Status` to avoid the allocations
-    fn correct_word_with_dict(&self, word: UniCase<&str>) -> Option<&'static [&'static str]> {
-        typos_dict::WORD.find(&word).copied()
-    }
 }
 
 #[cfg(not(feature = "dict"))]
@@ -70,18 +75,66 @@ impl BuiltIn {
     fn correct_ident_with_dict<'s>(&self, _ident: &str) -> Option<Status<'s>> {
         None
     }
+}
 
-    fn correct_word_with_dict(
-        &self,
-        _word: unicase::UniCase<&str>,
-    ) -> Option<&'static [&'static str]> {
-        None
+impl BuiltIn {
+    // Not using `Status` to avoid the allocations
+    fn correct_word_with_dicts(&self, word: UniCase<&str>) -> Option<Cow<'static, [&'static str]>> {
+        let mut found: Option<Cow<'static, [&'static str]>> = None;
+        for dictionary in self.dictionaries.iter() {
+            let Some(corrections) = correct_word_with_dict(*dictionary, word) else {
+                continue;
+            };
+            found = match found {
+                None => Some(Cow::Borrowed(corrections)),
+                // Already invalid
+                Some(found) if found.is_empty() => Some(found),
+                Some(found) => {
+                    let mut merged = found.into_owned();
+                    for correction in corrections {
+                        if !merged
+                            .iter()
+                            .any(|c| UniCase::new(*c) == UniCase::new(*correction))
+                        {
+                            merged.push(correction);
+                        }
+                    }
+                    Some(Cow::Owned(merged))
+                }
+            };
+        }
+        found
+    }
+}
+
+#[allow(unreachable_patterns)]
+fn correct_word_with_dict(
+    dictionary: crate::config::Dictionary,
+    word: UniCase<&str>,
+) -> Option<&'static [&'static str]> {
+    match dictionary {
+        #[cfg(feature = "dict")]
+        crate::config::Dictionary::Typos => typos_dict::WORD.find(&word).copied(),
+        #[cfg(feature = "codespell")]
+        crate::config::Dictionary::Codespell => {
+            codespell_dict::WORD_DICTIONARY.find(&word).copied()
+        }
+        #[cfg(feature = "misspell")]
+        crate::config::Dictionary::Misspell => misspell_dict::MAIN_DICTIONARY.find(&word).copied(),
+        #[cfg(feature = "wikipedia")]
+        crate::config::Dictionary::Wikipedia => {
+            wikipedia_dict::WORD_DICTIONARY.find(&word).copied()
+        }
+        _ => {
+            let _ = word;
+            None
+        }
     }
 }
 
 #[cfg(feature = "vars")]
 impl BuiltIn {
-    fn chain_with_vars(&self, corrections: &'static [&'static str]) -> Status<'static> {
+    fn chain_with_vars(&self, corrections: &[&'static str]) -> Status<'static> {
         if self.is_vars_enabled() {
             let mut chained: Vec<_> = corrections
                 .iter()
@@ -165,7 +218,7 @@ impl BuiltIn {
 
 #[cfg(not(feature = "vars"))]
 impl BuiltIn {
-    fn chain_with_vars(&self, corrections: &'static [&'static str]) -> Status<'static> {
+    fn chain_with_vars(&self, corrections: &[&'static str]) -> Status<'static> {
         Status::Corrections(corrections.iter().map(|c| Cow::Borrowed(*c)).collect())
     }
 
@@ -216,6 +269,7 @@ pub struct Override<'i, 'w, D> {
     identifiers: HashMap<&'i str, Status<'i>, ahash::RandomState>,
     ignored_words: Vec<regex::Regex>,
     words: HashMap<UniCase<&'w str>, Status<'w>, ahash::RandomState>,
+    preferred: PreferredCorrections<'w>,
     inner: D,
 }
 
@@ -226,6 +280,7 @@ impl<'i, 'w, D: typos::Dictionary> Override<'i, 'w, D> {
             identifiers: Default::default(),
             ignored_words: Default::default(),
             words: Default::default(),
+            preferred: Default::default(),
             inner,
         }
     }
@@ -248,6 +303,49 @@ impl<'i, 'w, D: typos::Dictionary> Override<'i, 'w, D> {
             .collect();
     }
 
+    /// Only typos with several corrections, including the preferred one, are kept, so call this
+    /// after all words are added
+    pub fn preferred_corrections<I: Iterator<Item = (&'w str, &'w str)>>(&mut self, preferred: I) {
+        let preferred = preferred
+            .filter(|(typo, correction)| {
+                let status = match self.words.get(&UniCase::new(*typo)) {
+                    Some(status) => Some(status.borrow()),
+                    None => self
+                        .inner
+                        .correct_word(typos::tokens::Word::new_unchecked(typo, Case::Lower, 0)),
+                };
+                let is_narrowed = match status {
+                    Some(Status::Corrections(corrections)) => {
+                        1 < corrections.len()
+                            && corrections
+                                .iter()
+                                .any(|c| UniCase::new(c.as_ref()) == UniCase::new(*correction))
+                    }
+                    _ => false,
+                };
+                if !is_narrowed {
+                    log::debug!(
+                        "preferred correction `{correction}` is not one of several corrections for `{typo}`"
+                    );
+                }
+                is_narrowed
+            })
+            .map(|(typo, correction)| (UniCase::new(typo), correction))
+            .collect();
+        self.preferred = PreferredCorrections(preferred);
+    }
+
+    pub fn get_preferred_corrections(&self) -> &PreferredCorrections<'w> {
+        &self.preferred
+    }
+
+    /// Add words from [`DictFile`]s, without replacing words from [`Override::words`]
+    pub fn dict_file_words<I: Iterator<Item = (&'w str, Status<'w>)>>(&mut self, words: I) {
+        for (typo, status) in words {
+            self.words.entry(UniCase::new(typo)).or_insert(status);
+        }
+    }
+
     fn interpret<'z, I: Iterator<Item = (&'z str, &'z str)>>(
         cases: I,
     ) -> impl Iterator<Item = (&'z str, Status<'z>)> + use<'z, I, D> {
@@ -301,11 +399,276 @@ impl<D: typos::Dictionary> typos::Dictionary for Override<'_, '_, D> {
                 for s in corrections.corrections_mut() {
                     case_correct(s, word_token.case());
                 }
-                return Some(corrections);
+                return Some(self.preferred.resolve(word_token.token(), corrections));
+            }
+        }
+
+        let status = self.inner.correct_word(word_token)?;
+        Some(self.preferred.resolve(word_token.token(), status))
+    }
+}
+
+/// The correction to use for typos with several, from `preferred-corrections`
+#[derive(Clone, Debug, Default)]
+pub struct PreferredCorrections<'w>(HashMap<UniCase<&'w str>, &'w str, ahash::RandomState>);
+
+impl<'w> PreferredCorrections<'w> {
+    /// Narrow `status` down to the preferred correction for `typo`, when it is one of them
+    pub fn resolve<'s>(&self, typo: &str, status: Status<'s>) -> Status<'s> {
+        // Skip hashing if we can
+        if self.0.is_empty() {
+            return status;
+        }
+        let Status::Corrections(mut corrections) = status else {
+            return status;
+        };
+        if 1 < corrections.len() {
+            if let Some(preferred) = self.0.get(&UniCase::new(typo)) {
+                if let Some(i) = corrections
+                    .iter()
+                    .position(|c| UniCase::new(c.as_ref()) == UniCase::new(*preferred))
+                {
+                    let correction = corrections.swap_remove(i);
+                    return Status::Corrections(vec![correction]);
+                }
+            }
+        }
+        Status::Corrections(corrections)
+    }
+
+    /// Whether `status` was narrowed down to the preferred correction for `typo`
+    pub fn is_preferred(&self, typo: &str, status: &Status<'_>) -> bool {
+        if self.0.is_empty() {
+            return false;
+        }
+        match (status, self.0.get(&UniCase::new(typo))) {
+            (Status::Corrections(corrections), Some(preferred)) => {
+                corrections.len() == 1
+                    && UniCase::new(corrections[0].as_ref()) == UniCase::new(*preferred)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// How much more likely the best correction must be than the next, to be used when fixing
+const RANK_CONFIDENCE_MARGIN: f64 = 0.75;
+
+/// Order `corrections` for `typo` by how likely each is to be what was meant
+///
+/// Corrections are favored when they already appear in `counts`, for the file, or `repo`, and
+/// when they are closer to the typo, where swapped letters and neighboring keys on a QWERTY
+/// keyboard count as nearer.
+/// As `repo` includes the file, uses within the file count for more.
+/// Ties keep their original order.
+///
+/// Returns whether the first correction is clearly the best.
+pub fn rank_corrections(
+    typo: &str,
+    corrections: &mut [Cow<'_, str>],
+    counts: &WordCounts<'_>,
+    repo: Option<&RepoWordCounts>,
+) -> bool {
+    if corrections.len() < 2 {
+        return true;
+    }
+    let mut scored: Vec<_> = corrections
+        .iter()
+        .map(|correction| {
+            let count = counts.get(correction) + repo.map(|repo| repo.get(correction)).unwrap_or(0);
+            let frequency = (count as f64).ln_1p();
+            let score = frequency - typing_distance(typo, correction);
+            (score, correction.clone())
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let confident = RANK_CONFIDENCE_MARGIN <= scored[0].0 - scored[1].0;
+    for (slot, (_, correction)) in corrections.iter_mut().zip(scored) {
+        *slot = correction;
+    }
+    confident
+}
+
+/// How often each word appears in a buffer, ignoring case
+#[derive(Clone, Debug, Default)]
+pub struct WordCounts<'b>(HashMap<UniCase<&'b str>, usize, ahash::RandomState>);
+
+impl<'b> WordCounts<'b> {
+    pub fn from_buffer(buffer: &'b [u8]) -> Self {
+        let mut counts = HashMap::<_, usize, _>::default();
+        for chunk in buffer.utf8_chunks() {
+            for word in chunk
+                .valid()
+                .split(|c: char| !c.is_alphabetic())
+                .filter(|w| !w.is_empty())
+            {
+                *counts.entry(UniCase::new(word)).or_default() += 1;
+            }
+        }
+        Self(counts)
+    }
+
+    pub fn get(&self, word: &str) -> usize {
+        self.0.get(&UniCase::new(word)).copied().unwrap_or(0)
+    }
+}
+
+/// How often each word appears across the files being checked, ignoring case
+#[derive(Clone, Debug, Default)]
+pub struct RepoWordCounts(HashMap<String, usize, ahash::RandomState>);
+
+impl RepoWordCounts {
+    pub fn add(&mut self, counts: &WordCounts<'_>) {
+        for (word, count) in counts.0.iter() {
+            *self.0.entry(word.to_lowercase()).or_default() += count;
+        }
+    }
+
+    pub fn extend(&mut self, other: Self) {
+        for (word, count) in other.0 {
+            *self.0.entry(word).or_default() += count;
+        }
+    }
+
+    pub fn get(&self, word: &str) -> usize {
+        self.0.get(&word.to_lowercase()).copied().unwrap_or(0)
+    }
+}
+
+/// Edit distance, with transpositions and substituting neighboring keys costing less
+fn typing_distance(typo: &str, correction: &str) -> f64 {
+    const EDIT: f64 = 1.0;
+    const NEAR_EDIT: f64 = 0.5;
+
+    let a: Vec<char> = typo.chars().flat_map(char::to_lowercase).collect();
+    let b: Vec<char> = correction.chars().flat_map(char::to_lowercase).collect();
+    // Optimal string alignment, keeping the last two rows
+    let mut prev_prev = vec![0.0; b.len() + 1];
+    let mut prev: Vec<f64> = (0..=b.len()).map(|j| j as f64 * EDIT).collect();
+    for i in 1..=a.len() {
+        let mut current = vec![i as f64 * EDIT; b.len() + 1];
+        for j in 1..=b.len() {
+            let substitution = if a[i - 1] == b[j - 1] {
+                0.0
+            } else if is_adjacent_key(a[i - 1], b[j - 1]) {
+                NEAR_EDIT
+            } else {
+                EDIT
+            };
+            let mut cost = (prev[j] + EDIT)
+                .min(current[j - 1] + EDIT)
+                .min(prev[j - 1] + substitution);
+            if 1 < i && 1 < j && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cost = cost.min(prev_prev[j - 2] + NEAR_EDIT);
+            }
+            current[j] = cost;
+        }
+        prev_prev = std::mem::replace(&mut prev, current);
+    }
+    prev[b.len()]
+}
+
+fn is_adjacent_key(a: char, b: char) -> bool {
+    const ROWS: [&str; 4] = ["1234567890", "qwertyuiop", "asdfghjkl", "zxcvbnm"];
+    let position = |c: char| {
+        ROWS.iter()
+            .enumerate()
+            .find_map(|(row, keys)| keys.find(c).map(|col| (row as isize, col as isize)))
+    };
+    let (Some((a_row, a_col)), Some((b_row, b_col))) = (position(a), position(b)) else {
+        return false;
+    };
+    // Each row is shifted right of the one above, by about half a key
+    match b_row - a_row {
+        0 => (a_col - b_col).abs() == 1,
+        1 => b_col == a_col || b_col == a_col - 1,
+        -1 => b_col == a_col || b_col == a_col + 1,
+        _ => false,
+    }
+}
+
+/// Words loaded from an `extend-dict-files` entry
+///
+/// Files ending in `.csv` are `typo,correction...` rows, like `typos-dict`'s `words.csv`.
+/// Anything else is a list of valid words, one per line.
+/// Blank lines and lines starting with `#` are skipped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DictFile {
+    /// Typos and their corrections, with valid words having no corrections
+    pub entries: Vec<(String, Vec<String>)>,
+}
+
+impl DictFile {
+    pub fn from_file(path: &std::path::Path) -> Result<Self, anyhow::Error> {
+        let content = std::fs::read_to_string(path).map_err(|err| {
+            let kind = err.kind();
+            std::io::Error::new(
+                kind,
+                format!("could not read dictionary at `{}`", path.display()),
+            )
+        })?;
+        let is_csv = path.extension().is_some_and(|ext| ext == "csv");
+        Self::parse(&content, is_csv).map_err(|err| anyhow::format_err!("{}:{err}", path.display()))
+    }
+
+    /// Errors are prefixed with the line number
+    pub fn parse(content: &str, is_csv: bool) -> Result<Self, anyhow::Error> {
+        let mut entries = Vec::new();
+        for (line_num, line) in content.lines().enumerate() {
+            let line_num = line_num + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if is_csv {
+                let mut fields = line.split(',').map(str::trim);
+                let typo = fields.next().unwrap_or_default();
+                let corrections: Vec<_> = fields.map(ToOwned::to_owned).collect();
+                if typo.is_empty()
+                    || corrections.is_empty()
+                    || corrections.iter().any(|c| c.is_empty())
+                {
+                    anyhow::bail!("{line_num}: expected `typo,correction...`, got `{line}`");
+                }
+                if let Some(field) = std::iter::once(typo)
+                    .chain(corrections.iter().map(String::as_str))
+                    .find(|field| field.contains(char::is_whitespace) || field.contains('"'))
+                {
+                    anyhow::bail!("{line_num}: invalid word `{field}`");
+                }
+                entries.push((typo.to_owned(), corrections));
+            } else {
+                if line.contains(char::is_whitespace) {
+                    anyhow::bail!("{line_num}: expected one word per line, got `{line}`");
+                }
+                entries.push((line.to_owned(), Vec::new()));
             }
         }
+        Ok(Self { entries })
+    }
 
-        self.inner.correct_word(word_token)
+    /// Convert to [`Override::dict_file_words`] entries, storing strings with `intern`
+    pub fn statuses<'s>(&self, intern: impl Fn(&str) -> &'s str) -> Vec<(&'s str, Status<'s>)> {
+        self.entries
+            .iter()
+            .map(|(typo, corrections)| {
+                let status = if corrections.is_empty()
+                    || corrections
+                        .iter()
+                        .any(|c| UniCase::new(c) == UniCase::new(typo))
+                {
+                    Status::Valid
+                } else {
+                    Status::Corrections(
+                        corrections
+                            .iter()
+                            .map(|c| Cow::Borrowed(intern(c)))
+                            .collect(),
+                    )
+                };
+                (intern(typo), status)
+            })
+            .collect()
     }
 }
 
@@ -313,6 +676,41 @@ impl<D: typos::Dictionary> typos::Dictionary for Override<'_, '_, D> {
 mod test {
     use super::*;
 
+    #[test]
+    fn test_preferred_corrections() {
+        use typos::Dictionary as _;
+
+        let mut words = HashMap::<&str, Status<'_>, ahash::RandomState>::default();
+        words.insert(
+            "abck",
+            Status::Corrections(vec!["back".into(), "aback".into()]),
+        );
+        words.insert("hte", Status::Corrections(vec!["the".into()]));
+        let mut dict = Override::new(BuiltIn::new(crate::config::Locale::default()));
+        dict.dict_file_words(words.into_iter());
+        dict.preferred_corrections([("abck", "aback"), ("teh", "tea"), ("hte", "the")].into_iter());
+
+        let correction =
+            dict.correct_word(typos::tokens::Word::new_unchecked("Abck", Case::Title, 0));
+        let expected = Status::Corrections(vec!["Aback".into()]);
+        assert_eq!(correction, Some(expected.clone()));
+        assert!(
+            dict.get_preferred_corrections()
+                .is_preferred("Abck", &expected)
+        );
+
+        let preferred = dict.get_preferred_corrections();
+        let unlisted = Status::Corrections(vec!["the".into(), "ten".into()]);
+        assert_eq!(preferred.resolve("teh", unlisted.clone()), unlisted);
+
+        // Already a single correction, so not narrowed
+        let correction =
+            dict.correct_word(typos::tokens::Word::new_unchecked("hte", Case::Lower, 0));
+        let expected = Status::Corrections(vec!["the".into()]);
+        assert_eq!(correction, Some(expected.clone()));
+        assert!(!preferred.is_preferred("hte", &expected));
+    }
+
     #[cfg(feature = "dict")]
     #[test]
     fn test_dict_correct() {
@@ -382,6 +780,44 @@ mod test {
         );
     }
 
+    #[cfg(feature = "codespell")]
+    #[test]
+    fn test_dict_codespell_opt_in() {
+        let word = typos::tokens::Word::new_unchecked("additionals", Case::Lower, 0);
+        let dict = BuiltIn::new(crate::config::Locale::En);
+        assert_eq!(dict.correct_word(word), None);
+
+        let dict = BuiltIn::new(crate::config::Locale::En).with_dictionaries(vec![
+            crate::config::Dictionary::Typos,
+            crate::config::Dictionary::Codespell,
+        ]);
+        assert_eq!(
+            dict.correct_word(word),
+            Some(Status::Corrections(vec!["additional".into()]))
+        );
+    }
+
+    #[cfg(all(feature = "dict", feature = "codespell"))]
+    #[test]
+    fn test_dict_merge_corrections() {
+        let dict = BuiltIn::new(crate::config::Locale::En).with_dictionaries(vec![
+            crate::config::Dictionary::Typos,
+            crate::config::Dictionary::Codespell,
+        ]);
+        let correction = dict.correct_word(typos::tokens::Word::new_unchecked(
+            "acessors",
+            Case::Lower,
+            0,
+        ));
+        assert_eq!(
+            correction,
+            Some(Status::Corrections(vec![
+                "accessors".into(),
+                "accessor".into()
+            ]))
+        );
+    }
+
     #[test]
     fn test_case_correct() {
         let cases = [
@@ -401,4 +837,84 @@ mod test {
             assert_eq!(*expected, actual);
         }
     }
+
+    #[test]
+    fn test_dict_file_words() {
+        let file = DictFile::parse("# Domain terms\nkubectl\n\nhelmfile\n", false).unwrap();
+        assert_eq!(
+            file.entries,
+            vec![
+                ("kubectl".to_owned(), vec![]),
+                ("helmfile".to_owned(), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dict_file_csv() {
+        let file = DictFile::parse("teh,the\nabandonned,abandoned, abandon\n", true).unwrap();
+        assert_eq!(
+            file.entries,
+            vec![
+                ("teh".to_owned(), vec!["the".to_owned()]),
+                (
+                    "abandonned".to_owned(),
+                    vec!["abandoned".to_owned(), "abandon".to_owned()]
+                ),
+            ]
+        );
+        let statuses = file.statuses(|s| Box::leak(s.to_owned().into_boxed_str()));
+        assert_eq!(
+            statuses[0],
+            ("teh", Status::Corrections(vec![Cow::Borrowed("the")]))
+        );
+    }
+
+    #[test]
+    fn test_dict_file_errors() {
+        let err = DictFile::parse("teh,the\nmissing\n", true).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2: expected `typo,correction...`, got `missing`"
+        );
+        let err = DictFile::parse("one\ntwo words\n", false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "2: expected one word per line, got `two words`"
+        );
+    }
+
+    #[test]
+    fn test_rank_corrections() {
+        let mut corrections: Vec<Cow<'_, str>> = vec!["aback".into(), "back".into()];
+        let counts = WordCounts::from_buffer(b"abck");
+        // Swapping letters is a more likely mistake than dropping one
+        assert!(!rank_corrections("abck", &mut corrections, &counts, None));
+        assert_eq!(corrections, ["back", "aback"]);
+
+        let counts = WordCounts::from_buffer(b"Aback, taken aback, aback again, abck");
+        assert!(rank_corrections("abck", &mut corrections, &counts, None));
+        assert_eq!(corrections, ["aback", "back"]);
+
+        // Uses elsewhere in the repository count too
+        let mut corrections: Vec<Cow<'_, str>> = vec!["back".into(), "aback".into()];
+        let counts = WordCounts::from_buffer(b"abck");
+        let mut repo = RepoWordCounts::default();
+        repo.add(&WordCounts::from_buffer(b"Aback, taken aback, aback again"));
+        assert!(rank_corrections(
+            "abck",
+            &mut corrections,
+            &counts,
+            Some(&repo)
+        ));
+        assert_eq!(corrections, ["aback", "back"]);
+    }
+
+    #[test]
+    fn test_typing_distance() {
+        assert_eq!(typing_distance("teh", "the"), 0.5);
+        assert_eq!(typing_distance("Tge", "the"), 0.5);
+        assert_eq!(typing_distance("tpe", "the"), 1.0);
+        assert_eq!(typing_distance("th", "the"), 1.0);
+    }
 }
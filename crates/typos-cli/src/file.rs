@@ -25,14 +25,16 @@ impl FileChecker for Typos {
         policy: &crate::policy::Policy<'_, '_, '_>,
         reporter: &dyn report::Report,
     ) -> Result<(), std::io::Error> {
-        if policy.check_filenames {
+        if policy.check_filenames && is_changed_filename(policy) {
             if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
-                for typo in check_str(file_name, policy) {
+                let mut ignores = None;
+                for typo in check_str(file_name, policy, &mut ignores, false) {
                     let msg = report::Typo {
                         context: Some(report::PathContext { path }.into()),
                         buffer: std::borrow::Cow::Borrowed(file_name.as_bytes()),
                         byte_offset: typo.byte_offset,
                         typo: typo.typo.as_ref(),
+                        preferred: is_preferred(policy, &typo),
                         corrections: typo.corrections,
                     };
                     reporter.report(msg.into())?;
@@ -40,25 +42,40 @@ impl FileChecker for Typos {
             }
         }
 
-        if policy.check_files {
+        if policy.check_files && !report_too_large(path, explicit, policy, reporter)? {
             let (buffer, content_type) = read_file(path, reporter)?;
             if !explicit && !policy.binary && content_type.is_binary() {
                 let msg = report::BinaryFile { path };
                 reporter.report(msg.into())?;
+            } else if report_skipped(path, explicit, &buffer, policy, reporter)? {
+                // Skipped
             } else {
                 let mut accum_line_num = AccumulateLineNum::new();
-                for typo in check_bytes(&buffer, policy) {
+                let mut ignores = None;
+                for typo in check_bytes(&buffer, policy, &mut ignores, false) {
                     let line_num = accum_line_num.line_num(&buffer, typo.byte_offset);
+                    if !is_changed_line(policy, line_num) {
+                        continue;
+                    }
                     let (line, line_offset) = extract_line(&buffer, typo.byte_offset);
                     let msg = report::Typo {
-                        context: Some(report::FileContext { path, line_num }.into()),
+                        context: Some(
+                            report::FileContext {
+                                path,
+                                line_num,
+                                line_start: typo.byte_offset - line_offset,
+                            }
+                            .into(),
+                        ),
                         buffer: std::borrow::Cow::Borrowed(line),
                         byte_offset: line_offset,
                         typo: typo.typo.as_ref(),
+                        preferred: is_preferred(policy, &typo),
                         corrections: typo.corrections,
                     };
                     reporter.report(msg.into())?;
                 }
+                report_unused_directives(path, &buffer, ignores, reporter)?;
             }
         }
 
@@ -66,6 +83,308 @@ impl FileChecker for Typos {
     }
 }
 
+/// Check files like [`Typos`], replaying the results of unchanged files from a [`Cache`]
+#[derive(Debug, Clone, Copy)]
+pub struct CachedTypos<'c> {
+    pub cache: &'c Cache,
+}
+
+impl FileChecker for CachedTypos<'_> {
+    fn check_file(
+        &self,
+        path: &std::path::Path,
+        explicit: bool,
+        policy: &crate::policy::Policy<'_, '_, '_>,
+        reporter: &dyn report::Report,
+    ) -> Result<(), std::io::Error> {
+        // Results for a subset of lines can't be replayed for another subset
+        let Some(fingerprint) = policy.fingerprint.filter(|_| policy.changes.is_none()) else {
+            return Typos.check_file(path, explicit, policy, reporter);
+        };
+        if path == std::path::Path::new("-") {
+            return Typos.check_file(path, explicit, policy, reporter);
+        }
+        // Only the file name is checked, so it isn't worth reading and hashing the contents
+        if policy.check_files && too_large(path, explicit, policy).is_some() {
+            return Typos.check_file(path, explicit, policy, reporter);
+        }
+        let (Ok(abs_path), Ok(content)) = (path.canonicalize(), std::fs::read(path)) else {
+            return Typos.check_file(path, explicit, policy, reporter);
+        };
+
+        let key = {
+            use siphasher::sip128::Hasher128 as _;
+            use std::hash::Hasher as _;
+
+            let mut hasher = siphasher::sip128::SipHasher13::new();
+            hasher.write(&content);
+            hasher.write_u128(fingerprint);
+            hasher.write(policy.file_type.unwrap_or_default().as_bytes());
+            hasher.write_u8(explicit as u8);
+            hasher.write(policy.generated_attribute.unwrap_or_default().as_bytes());
+            format!("{:032x}", hasher.finish128().as_u128())
+        };
+        if let Some(messages) = self.cache.load(&abs_path, &key) {
+            log::debug!("{}: replaying cached results", path.display());
+            for message in messages.iter() {
+                reporter.report(message.to_message(path))?;
+            }
+            return Ok(());
+        }
+
+        let recorder = CacheRecorder {
+            messages: std::sync::Mutex::new(Some(Vec::new())),
+            reporter,
+        };
+        Typos.check_file(path, explicit, policy, &recorder)?;
+        if let Some(messages) = recorder.messages.into_inner().unwrap() {
+            if let Err(err) = self.cache.store(&abs_path, &key, messages) {
+                log::debug!("{}: could not cache results: {err}", path.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// On-disk results of [`CachedTypos`]
+///
+/// Entries are keyed by the file's content and the [`Policy`][crate::policy::Policy] it was
+/// checked with, so any change to either causes the file to be checked again.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: std::path::PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<std::path::PathBuf>) -> Result<Self, std::io::Error> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Each file has one entry, replaced when its key changes
+    fn entry_path(&self, abs_path: &std::path::Path) -> std::path::PathBuf {
+        use siphasher::sip128::Hasher128 as _;
+        use std::hash::Hasher as _;
+
+        let mut hasher = siphasher::sip128::SipHasher13::new();
+        hasher.write(abs_path.as_os_str().as_encoded_bytes());
+        self.dir
+            .join(format!("{:032x}.json", hasher.finish128().as_u128()))
+    }
+
+    fn load(&self, abs_path: &std::path::Path, key: &str) -> Option<Vec<CachedMessage>> {
+        let content = std::fs::read(self.entry_path(abs_path)).ok()?;
+        let entry: CacheEntry = serde_json::from_slice(&content).ok()?;
+        (entry.path == abs_path && entry.key == key).then_some(entry.messages)
+    }
+
+    fn store(
+        &self,
+        abs_path: &std::path::Path,
+        key: &str,
+        messages: Vec<CachedMessage>,
+    ) -> Result<(), std::io::Error> {
+        let entry = CacheEntry {
+            path: abs_path.to_owned(),
+            key: key.to_owned(),
+            messages,
+        };
+        let content = serde_json::to_vec(&entry)?;
+        let entry_path = self.entry_path(abs_path);
+        // Write then rename, so an interrupted run can't leave a partial entry behind
+        let tmp_path = entry_path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, content)?;
+        std::fs::rename(tmp_path, entry_path)
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CacheEntry {
+    path: std::path::PathBuf,
+    key: String,
+    messages: Vec<CachedMessage>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CachedMessage {
+    BinaryFile,
+    Skipped {
+        /// `None` for skipped files
+        line_num: Option<usize>,
+        #[serde(default)]
+        line_start: usize,
+        reason: String,
+    },
+    Typo {
+        /// `None` for typos in the file name
+        line_num: Option<usize>,
+        #[serde(default)]
+        line_start: usize,
+        buffer: Vec<u8>,
+        byte_offset: usize,
+        typo: String,
+        /// `None` for disallowed words
+        corrections: Option<Vec<String>>,
+        #[serde(default)]
+        preferred: bool,
+    },
+    UnusedDirective {
+        line_num: usize,
+        line_start: usize,
+        directive: String,
+    },
+}
+
+impl CachedMessage {
+    fn from_message(msg: &report::Message<'_>) -> Option<Self> {
+        match msg {
+            report::Message::BinaryFile(_) => Some(Self::BinaryFile),
+            report::Message::Skipped(msg) => {
+                let (line_num, line_start) = match msg.context.as_ref()? {
+                    report::Context::File(context) => (Some(context.line_num), context.line_start),
+                    report::Context::Path(_) => (None, 0),
+                };
+                Some(Self::Skipped {
+                    line_num,
+                    line_start,
+                    reason: msg.reason.as_ref().to_owned(),
+                })
+            }
+            report::Message::Typo(typo) => {
+                let (line_num, line_start) = match typo.context.as_ref()? {
+                    report::Context::File(context) => (Some(context.line_num), context.line_start),
+                    report::Context::Path(_) => (None, 0),
+                };
+                let corrections = match &typo.corrections {
+                    typos::Status::Valid => return None,
+                    typos::Status::Invalid => None,
+                    typos::Status::Corrections(corrections) => {
+                        Some(corrections.iter().map(|c| c.as_ref().to_owned()).collect())
+                    }
+                };
+                Some(Self::Typo {
+                    line_num,
+                    line_start,
+                    buffer: typo.buffer.as_ref().to_owned(),
+                    byte_offset: typo.byte_offset,
+                    typo: typo.typo.to_owned(),
+                    corrections,
+                    preferred: typo.preferred,
+                })
+            }
+            report::Message::UnusedDirective(msg) => match msg.context.as_ref()? {
+                report::Context::File(context) => Some(Self::UnusedDirective {
+                    line_num: context.line_num,
+                    line_start: context.line_start,
+                    directive: msg.directive.as_ref().to_owned(),
+                }),
+                report::Context::Path(_) => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn to_message<'m>(&'m self, path: &'m std::path::Path) -> report::Message<'m> {
+        match self {
+            Self::BinaryFile => report::BinaryFile { path }.into(),
+            Self::Skipped {
+                line_num,
+                line_start,
+                reason,
+            } => {
+                let context = match line_num {
+                    Some(line_num) => report::FileContext {
+                        path,
+                        line_num: *line_num,
+                        line_start: *line_start,
+                    }
+                    .into(),
+                    None => report::PathContext { path }.into(),
+                };
+                report::Skipped {
+                    context: Some(context),
+                    reason: std::borrow::Cow::Borrowed(reason),
+                }
+                .into()
+            }
+            Self::Typo {
+                line_num,
+                line_start,
+                buffer,
+                byte_offset,
+                typo,
+                corrections,
+                preferred,
+            } => {
+                let context = match line_num {
+                    Some(line_num) => report::FileContext {
+                        path,
+                        line_num: *line_num,
+                        line_start: *line_start,
+                    }
+                    .into(),
+                    None => report::PathContext { path }.into(),
+                };
+                let corrections = match corrections {
+                    Some(corrections) => typos::Status::Corrections(
+                        corrections
+                            .iter()
+                            .map(|c| std::borrow::Cow::Borrowed(c.as_str()))
+                            .collect(),
+                    ),
+                    None => typos::Status::Invalid,
+                };
+                report::Typo {
+                    context: Some(context),
+                    buffer: std::borrow::Cow::Borrowed(buffer),
+                    byte_offset: *byte_offset,
+                    typo,
+                    corrections,
+                    preferred: *preferred,
+                }
+                .into()
+            }
+            Self::UnusedDirective {
+                line_num,
+                line_start,
+                directive,
+            } => report::UnusedDirective {
+                context: Some(
+                    report::FileContext {
+                        path,
+                        line_num: *line_num,
+                        line_start: *line_start,
+                    }
+                    .into(),
+                ),
+                directive: std::borrow::Cow::Borrowed(directive),
+            }
+            .into(),
+        }
+    }
+}
+
+/// Record messages for [`Cache::store`], giving up on any that can't be replayed
+struct CacheRecorder<'r> {
+    messages: std::sync::Mutex<Option<Vec<CachedMessage>>>,
+    reporter: &'r dyn report::Report,
+}
+
+impl report::Report for CacheRecorder<'_> {
+    fn report(&self, msg: report::Message<'_>) -> Result<(), std::io::Error> {
+        let mut messages = self.messages.lock().unwrap();
+        if let Some(recorded) = messages.as_mut() {
+            match CachedMessage::from_message(&msg) {
+                Some(message) => recorded.push(message),
+                None => *messages = None,
+            }
+        }
+        self.reporter.report(msg)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct FixTypos;
 
@@ -77,30 +396,18 @@ impl FileChecker for FixTypos {
         policy: &crate::policy::Policy<'_, '_, '_>,
         reporter: &dyn report::Report,
     ) -> Result<(), std::io::Error> {
-        if policy.check_files {
+        if policy.check_files && !report_too_large(path, explicit, policy, reporter)? {
             let (buffer, content_type) = read_file(path, reporter)?;
             if !explicit && !policy.binary && content_type.is_binary() {
                 let msg = report::BinaryFile { path };
                 reporter.report(msg.into())?;
+            } else if report_skipped(path, explicit, &buffer, policy, reporter)? {
+                // Skipped
             } else {
-                let mut fixes = Vec::new();
-                let mut accum_line_num = AccumulateLineNum::new();
-                for typo in check_bytes(&buffer, policy) {
-                    if is_fixable(&typo) {
-                        fixes.push(typo.into_owned());
-                    } else {
-                        let line_num = accum_line_num.line_num(&buffer, typo.byte_offset);
-                        let (line, line_offset) = extract_line(&buffer, typo.byte_offset);
-                        let msg = report::Typo {
-                            context: Some(report::FileContext { path, line_num }.into()),
-                            buffer: std::borrow::Cow::Borrowed(line),
-                            byte_offset: line_offset,
-                            typo: typo.typo.as_ref(),
-                            corrections: typo.corrections,
-                        };
-                        reporter.report(msg.into())?;
-                    }
-                }
+                let fixes =
+                    collect_fixes(path, &buffer, true, policy, reporter, |msg, typo, fixes| {
+                        fix_or_report(msg, typo, fixes, reporter)
+                    })?;
                 if !fixes.is_empty() || path == std::path::Path::new("-") {
                     let buffer = fix_buffer(buffer, fixes.into_iter());
                     write_file(path, content_type, buffer, reporter)?;
@@ -109,29 +416,204 @@ impl FileChecker for FixTypos {
         }
 
         // Ensure the above write can happen before renaming the file.
-        if policy.check_filenames {
+        if policy.check_filenames && is_changed_filename(policy) {
             if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
-                let mut fixes = Vec::new();
-                for typo in check_str(file_name, policy) {
-                    if is_fixable(&typo) {
-                        fixes.push(typo.into_owned());
-                    } else {
-                        let msg = report::Typo {
-                            context: Some(report::PathContext { path }.into()),
-                            buffer: std::borrow::Cow::Borrowed(file_name.as_bytes()),
-                            byte_offset: typo.byte_offset,
-                            typo: typo.typo.as_ref(),
-                            corrections: typo.corrections,
-                        };
-                        reporter.report(msg.into())?;
-                    }
+                let fixes =
+                    collect_file_name_fixes(path, file_name, true, policy, |msg, typo, fixes| {
+                        fix_or_report(msg, typo, fixes, reporter)
+                    })?;
+                if !fixes.is_empty() {
+                    let new_path = fix_file_name(path, file_name, fixes);
+                    std::fs::rename(path, new_path)?;
                 }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Find the typos in a file's contents, letting `choose` queue a fix for or report each one
+///
+/// Typos on lines outside of `--diff-base` are skipped.
+fn collect_fixes(
+    path: &std::path::Path,
+    buffer: &[u8],
+    fix: bool,
+    policy: &crate::policy::Policy<'_, '_, '_>,
+    reporter: &dyn report::Report,
+    mut choose: impl FnMut(
+        report::Typo<'_>,
+        typos::Typo<'_>,
+        &mut Vec<typos::Typo<'static>>,
+    ) -> Result<(), std::io::Error>,
+) -> Result<Vec<typos::Typo<'static>>, std::io::Error> {
+    let mut fixes = Vec::new();
+    let mut accum_line_num = AccumulateLineNum::new();
+    let mut ignores = None;
+    for typo in check_bytes(buffer, policy, &mut ignores, fix) {
+        let line_num = accum_line_num.line_num(buffer, typo.byte_offset);
+        if !is_changed_line(policy, line_num) {
+            continue;
+        }
+        let (line, line_offset) = extract_line(buffer, typo.byte_offset);
+        let msg = report::Typo {
+            context: Some(
+                report::FileContext {
+                    path,
+                    line_num,
+                    line_start: typo.byte_offset - line_offset,
+                }
+                .into(),
+            ),
+            buffer: std::borrow::Cow::Borrowed(line),
+            byte_offset: line_offset,
+            typo: typo.typo.as_ref(),
+            corrections: typo.corrections.borrow(),
+            preferred: is_preferred(policy, &typo),
+        };
+        choose(msg, typo.borrow(), &mut fixes)?;
+    }
+    report_unused_directives(path, buffer, ignores, reporter)?;
+    Ok(fixes)
+}
+
+/// Find the typos in a file's name, letting `choose` queue a fix for or report each one
+fn collect_file_name_fixes(
+    path: &std::path::Path,
+    file_name: &str,
+    fix: bool,
+    policy: &crate::policy::Policy<'_, '_, '_>,
+    mut choose: impl FnMut(
+        report::Typo<'_>,
+        typos::Typo<'_>,
+        &mut Vec<typos::Typo<'static>>,
+    ) -> Result<(), std::io::Error>,
+) -> Result<Vec<typos::Typo<'static>>, std::io::Error> {
+    let mut fixes = Vec::new();
+    let mut ignores = None;
+    for typo in check_str(file_name, policy, &mut ignores, fix) {
+        let msg = report::Typo {
+            context: Some(report::PathContext { path }.into()),
+            buffer: std::borrow::Cow::Borrowed(file_name.as_bytes()),
+            byte_offset: typo.byte_offset,
+            typo: typo.typo.as_ref(),
+            corrections: typo.corrections.borrow(),
+            preferred: is_preferred(policy, &typo),
+        };
+        choose(msg, typo.borrow(), &mut fixes)?;
+    }
+    Ok(fixes)
+}
+
+/// Queue the fix for a typo with a single correction, reporting the rest
+fn fix_or_report(
+    msg: report::Typo<'_>,
+    typo: typos::Typo<'_>,
+    fixes: &mut Vec<typos::Typo<'static>>,
+    reporter: &dyn report::Report,
+) -> Result<(), std::io::Error> {
+    if is_fixable(&typo) {
+        fixes.push(typo.into_owned());
+    } else {
+        reporter.report(msg.into())?;
+    }
+    Ok(())
+}
+
+fn fix_file_name(
+    path: &std::path::Path,
+    file_name: &str,
+    fixes: Vec<typos::Typo<'static>>,
+) -> std::path::PathBuf {
+    let file_name = file_name.to_owned().into_bytes();
+    let new_name = fix_buffer(file_name, fixes.into_iter());
+    let new_name = String::from_utf8(new_name).expect("corrections are valid utf-8");
+    path.with_file_name(new_name)
+}
+
+/// Decide how to handle each typo, like by prompting the user
+pub trait Fixer: Send + Sync {
+    fn fix(&self, typo: &report::Typo<'_>) -> Result<Fix, std::io::Error>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fix {
+    /// Replace the typo
+    Correct(String),
+    /// Leave the typo, still reporting it
+    Skip,
+    /// Leave the typo, treating it as valid
+    Ignore,
+}
+
+/// Fix typos, as chosen by a [`Fixer`]
+#[derive(Clone, Copy)]
+pub struct InteractiveFixTypos<'f> {
+    pub fixer: &'f dyn Fixer,
+}
+
+impl InteractiveFixTypos<'_> {
+    fn choose(
+        &self,
+        msg: report::Typo<'_>,
+        typo: typos::Typo<'_>,
+        fixes: &mut Vec<typos::Typo<'static>>,
+        reporter: &dyn report::Report,
+    ) -> Result<(), std::io::Error> {
+        match self.fixer.fix(&msg)? {
+            Fix::Correct(correction) => {
+                let mut typo = typo.into_owned();
+                typo.corrections = typos::Status::Corrections(vec![correction.into()]);
+                fixes.push(typo);
+            }
+            Fix::Skip => reporter.report(msg.into())?,
+            Fix::Ignore => {}
+        }
+        Ok(())
+    }
+}
+
+impl FileChecker for InteractiveFixTypos<'_> {
+    fn check_file(
+        &self,
+        path: &std::path::Path,
+        explicit: bool,
+        policy: &crate::policy::Policy<'_, '_, '_>,
+        reporter: &dyn report::Report,
+    ) -> Result<(), std::io::Error> {
+        if policy.check_files && !report_too_large(path, explicit, policy, reporter)? {
+            let (buffer, content_type) = read_file(path, reporter)?;
+            if !explicit && !policy.binary && content_type.is_binary() {
+                let msg = report::BinaryFile { path };
+                reporter.report(msg.into())?;
+            } else if report_skipped(path, explicit, &buffer, policy, reporter)? {
+                // Skipped
+            } else {
+                let fixes = collect_fixes(
+                    path,
+                    &buffer,
+                    false,
+                    policy,
+                    reporter,
+                    |msg, typo, fixes| self.choose(msg, typo, fixes, reporter),
+                )?;
                 if !fixes.is_empty() {
-                    let file_name = file_name.to_owned().into_bytes();
-                    let new_name = fix_buffer(file_name, fixes.into_iter());
-                    let new_name =
-                        String::from_utf8(new_name).expect("corrections are valid utf-8");
-                    let new_path = path.with_file_name(new_name);
+                    let buffer = fix_buffer(buffer, fixes.into_iter());
+                    write_file(path, content_type, buffer, reporter)?;
+                }
+            }
+        }
+
+        // Ensure the above write can happen before renaming the file.
+        if policy.check_filenames && is_changed_filename(policy) {
+            if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
+                let fixes =
+                    collect_file_name_fixes(path, file_name, false, policy, |msg, typo, fixes| {
+                        self.choose(msg, typo, fixes, reporter)
+                    })?;
+                if !fixes.is_empty() {
+                    let new_path = fix_file_name(path, file_name, fixes);
                     std::fs::rename(path, new_path)?;
                 }
             }
@@ -154,30 +636,18 @@ impl FileChecker for DiffTypos {
     ) -> Result<(), std::io::Error> {
         let mut content = Vec::new();
         let mut new_content = Vec::new();
-        if policy.check_files {
+        if policy.check_files && !report_too_large(path, explicit, policy, reporter)? {
             let (buffer, content_type) = read_file(path, reporter)?;
             if !explicit && !policy.binary && content_type.is_binary() {
                 let msg = report::BinaryFile { path };
                 reporter.report(msg.into())?;
+            } else if report_skipped(path, explicit, &buffer, policy, reporter)? {
+                // Skipped
             } else {
-                let mut fixes = Vec::new();
-                let mut accum_line_num = AccumulateLineNum::new();
-                for typo in check_bytes(&buffer, policy) {
-                    if is_fixable(&typo) {
-                        fixes.push(typo.into_owned());
-                    } else {
-                        let line_num = accum_line_num.line_num(&buffer, typo.byte_offset);
-                        let (line, line_offset) = extract_line(&buffer, typo.byte_offset);
-                        let msg = report::Typo {
-                            context: Some(report::FileContext { path, line_num }.into()),
-                            buffer: std::borrow::Cow::Borrowed(line),
-                            byte_offset: line_offset,
-                            typo: typo.typo.as_ref(),
-                            corrections: typo.corrections,
-                        };
-                        reporter.report(msg.into())?;
-                    }
-                }
+                let fixes =
+                    collect_fixes(path, &buffer, true, policy, reporter, |msg, typo, fixes| {
+                        fix_or_report(msg, typo, fixes, reporter)
+                    })?;
                 if !fixes.is_empty() {
                     new_content = fix_buffer(buffer.clone(), fixes.into_iter());
                     content = buffer;
@@ -187,29 +657,14 @@ impl FileChecker for DiffTypos {
 
         // Match FixTypos ordering for easy diffing.
         let mut new_path = None;
-        if policy.check_filenames {
+        if policy.check_filenames && is_changed_filename(policy) {
             if let Some(file_name) = path.file_name().and_then(|s| s.to_str()) {
-                let mut fixes = Vec::new();
-                for typo in check_str(file_name, policy) {
-                    if is_fixable(&typo) {
-                        fixes.push(typo.into_owned());
-                    } else {
-                        let msg = report::Typo {
-                            context: Some(report::PathContext { path }.into()),
-                            buffer: std::borrow::Cow::Borrowed(file_name.as_bytes()),
-                            byte_offset: typo.byte_offset,
-                            typo: typo.typo.as_ref(),
-                            corrections: typo.corrections,
-                        };
-                        reporter.report(msg.into())?;
-                    }
-                }
+                let fixes =
+                    collect_file_name_fixes(path, file_name, true, policy, |msg, typo, fixes| {
+                        fix_or_report(msg, typo, fixes, reporter)
+                    })?;
                 if !fixes.is_empty() {
-                    let file_name = file_name.to_owned().into_bytes();
-                    let new_name = fix_buffer(file_name, fixes.into_iter());
-                    let new_name =
-                        String::from_utf8(new_name).expect("corrections are valid utf-8");
-                    new_path = Some(path.with_file_name(new_name));
+                    new_path = Some(fix_file_name(path, file_name, fixes));
                 }
             }
         }
@@ -274,7 +729,7 @@ impl FileChecker for HighlightIdentifiers {
                             .get_or_insert_with(|| {
                                 Ignores::new(file_name.as_bytes(), policy.ignore)
                             })
-                            .is_ignored(word.span())
+                            .is_ignored(word.span(), word.token())
                     })
                     .zip(HIGHLIGHTS.iter().cycle())
                 {
@@ -309,10 +764,12 @@ impl FileChecker for HighlightIdentifiers {
             writeln!(handle, "{UNMATCHED}{}:{UNMATCHED:#}", path.display())?;
         }
 
-        if policy.check_files {
+        if policy.check_files && !report_too_large(path, explicit, policy, reporter)? {
             let (buffer, content_type) = read_file(path, reporter)?;
             if !explicit && !policy.binary && content_type.is_binary() {
                 // nop
+            } else if report_skipped(path, explicit, &buffer, policy, reporter)? {
+                // Skipped
             } else if let Ok(buffer) = buffer.to_str() {
                 let mut styled = String::new();
                 let mut prev_end = 0;
@@ -321,8 +778,8 @@ impl FileChecker for HighlightIdentifiers {
                     .parse_bytes(buffer.as_bytes())
                     .filter(|word| {
                         !ignores
-                            .get_or_insert_with(|| Ignores::new(buffer.as_bytes(), policy.ignore))
-                            .is_ignored(word.span())
+                            .get_or_insert_with(|| Ignores::for_buffer(buffer.as_bytes(), policy))
+                            .is_ignored(word.span(), word.token())
                     })
                     .zip(HIGHLIGHTS.iter().cycle())
                 {
@@ -369,7 +826,7 @@ impl FileChecker for Identifiers {
                 for word in policy.tokenizer.parse_str(file_name) {
                     if ignores
                         .get_or_insert_with(|| Ignores::new(file_name.as_bytes(), policy.ignore))
-                        .is_ignored(word.span())
+                        .is_ignored(word.span(), word.token())
                     {
                         continue;
                     }
@@ -383,16 +840,18 @@ impl FileChecker for Identifiers {
             }
         }
 
-        if policy.check_files {
+        if policy.check_files && !report_too_large(path, explicit, policy, reporter)? {
             let (buffer, content_type) = read_file(path, reporter)?;
             if !explicit && !policy.binary && content_type.is_binary() {
                 let msg = report::BinaryFile { path };
                 reporter.report(msg.into())?;
+            } else if report_skipped(path, explicit, &buffer, policy, reporter)? {
+                // Skipped
             } else {
                 for word in policy.tokenizer.parse_bytes(&buffer) {
                     if ignores
-                        .get_or_insert_with(|| Ignores::new(&buffer, policy.ignore))
-                        .is_ignored(word.span())
+                        .get_or_insert_with(|| Ignores::for_buffer(&buffer, policy))
+                        .is_ignored(word.span(), word.token())
                     {
                         continue;
                     }
@@ -401,7 +860,14 @@ impl FileChecker for Identifiers {
                     // anyway.
                     let line_num = 0;
                     let msg = report::Parse {
-                        context: Some(report::FileContext { path, line_num }.into()),
+                        context: Some(
+                            report::FileContext {
+                                path,
+                                line_num,
+                                line_start: 0,
+                            }
+                            .into(),
+                        ),
                         kind: report::ParseKind::Identifier,
                         data: word.token(),
                     };
@@ -444,7 +910,7 @@ impl FileChecker for HighlightWords {
                             .get_or_insert_with(|| {
                                 Ignores::new(file_name.as_bytes(), policy.ignore)
                             })
-                            .is_ignored(word.span())
+                            .is_ignored(word.span(), word.token())
                     })
                     .zip(HIGHLIGHTS.iter().cycle())
                 {
@@ -479,10 +945,12 @@ impl FileChecker for HighlightWords {
             writeln!(handle, "{UNMATCHED}{}:{UNMATCHED:#}", path.display())?;
         }
 
-        if policy.check_files {
+        if policy.check_files && !report_too_large(path, explicit, policy, reporter)? {
             let (buffer, content_type) = read_file(path, reporter)?;
             if !explicit && !policy.binary && content_type.is_binary() {
                 // nop
+            } else if report_skipped(path, explicit, &buffer, policy, reporter)? {
+                // Skipped
             } else if let Ok(buffer) = buffer.to_str() {
                 let mut styled = String::new();
                 let mut prev_end = 0;
@@ -492,8 +960,8 @@ impl FileChecker for HighlightWords {
                     .flat_map(|i| i.split())
                     .filter(|word| {
                         !ignores
-                            .get_or_insert_with(|| Ignores::new(buffer.as_bytes(), policy.ignore))
-                            .is_ignored(word.span())
+                            .get_or_insert_with(|| Ignores::for_buffer(buffer.as_bytes(), policy))
+                            .is_ignored(word.span(), word.token())
                     })
                     .zip(HIGHLIGHTS.iter().cycle())
                 {
@@ -553,7 +1021,7 @@ impl FileChecker for Words {
                 {
                     if ignores
                         .get_or_insert_with(|| Ignores::new(file_name.as_bytes(), policy.ignore))
-                        .is_ignored(word.span())
+                        .is_ignored(word.span(), word.token())
                     {
                         continue;
                     }
@@ -567,11 +1035,13 @@ impl FileChecker for Words {
             }
         }
 
-        if policy.check_files {
+        if policy.check_files && !report_too_large(path, explicit, policy, reporter)? {
             let (buffer, content_type) = read_file(path, reporter)?;
             if !explicit && !policy.binary && content_type.is_binary() {
                 let msg = report::BinaryFile { path };
                 reporter.report(msg.into())?;
+            } else if report_skipped(path, explicit, &buffer, policy, reporter)? {
+                // Skipped
             } else {
                 for word in policy
                     .tokenizer
@@ -579,8 +1049,8 @@ impl FileChecker for Words {
                     .flat_map(|i| i.split())
                 {
                     if ignores
-                        .get_or_insert_with(|| Ignores::new(&buffer, policy.ignore))
-                        .is_ignored(word.span())
+                        .get_or_insert_with(|| Ignores::for_buffer(&buffer, policy))
+                        .is_ignored(word.span(), word.token())
                     {
                         continue;
                     }
@@ -589,7 +1059,14 @@ impl FileChecker for Words {
                     // anyway.
                     let line_num = 0;
                     let msg = report::Parse {
-                        context: Some(report::FileContext { path, line_num }.into()),
+                        context: Some(
+                            report::FileContext {
+                                path,
+                                line_num,
+                                line_start: 0,
+                            }
+                            .into(),
+                        ),
                         kind: report::ParseKind::Word,
                         data: word.token(),
                     };
@@ -617,11 +1094,15 @@ impl FileChecker for FileTypes {
         if policy.binary {
             let msg = report::FileType::new(path, policy.file_type);
             reporter.report(msg.into())?;
+        } else if report_too_large(path, explicit, policy, reporter)? {
+            // Skipped
         } else {
-            let (_buffer, content_type) = read_file(path, reporter)?;
+            let (buffer, content_type) = read_file(path, reporter)?;
             if !explicit && content_type.is_binary() {
                 let msg = report::BinaryFile { path };
                 reporter.report(msg.into())?;
+            } else if report_skipped(path, explicit, &buffer, policy, reporter)? {
+                // Skipped
             } else {
                 let msg = report::FileType::new(path, policy.file_type);
                 reporter.report(msg.into())?;
@@ -647,11 +1128,15 @@ impl FileChecker for FoundFiles {
         if policy.binary {
             let msg = report::File::new(path);
             reporter.report(msg.into())?;
+        } else if report_too_large(path, explicit, policy, reporter)? {
+            // Skipped
         } else {
-            let (_buffer, content_type) = read_file(path, reporter)?;
+            let (buffer, content_type) = read_file(path, reporter)?;
             if !explicit && content_type.is_binary() {
                 let msg = report::BinaryFile { path };
                 reporter.report(msg.into())?;
+            } else if report_skipped(path, explicit, &buffer, policy, reporter)? {
+                // Skipped
             } else {
                 let msg = report::File::new(path);
                 reporter.report(msg.into())?;
@@ -662,7 +1147,7 @@ impl FileChecker for FoundFiles {
     }
 }
 
-fn read_file(
+pub(crate) fn read_file(
     path: &std::path::Path,
     reporter: &dyn report::Report,
 ) -> Result<(Vec<u8>, content_inspector::ContentType), std::io::Error> {
@@ -722,6 +1207,200 @@ fn read_file(
     Ok((buffer, content_type))
 }
 
+/// Markers that tools leave at the top of files they generate, and the reason to report
+const GENERATED_MARKERS: &[(&str, &str)] = &[
+    ("@generated", "has an `@generated` marker"),
+    ("<auto-generated", "has an `<auto-generated>` marker"),
+    (
+        "Generated by the protocol buffer compiler",
+        "was generated by `protoc`",
+    ),
+];
+
+/// Lockfiles that package managers generate
+///
+/// Most are also in the `lock` file type, whose contents aren't checked unless overridden.
+const LOCKFILE_NAMES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lock",
+    "composer.lock",
+    "Gemfile.lock",
+    "Pipfile.lock",
+    "poetry.lock",
+    "uv.lock",
+    "pdm.lock",
+    "go.sum",
+    "flake.lock",
+    "mix.lock",
+    "pubspec.lock",
+    "Podfile.lock",
+    "Package.resolved",
+    "packages.lock.json",
+    "gradle.lockfile",
+];
+
+/// How many lines at the top of a file to look for [`GENERATED_MARKERS`] in
+const GENERATED_HEAD_LINES: usize = 5;
+
+/// Lines at least this long at the top of a script, stylesheet, or data file mean it's minified
+const MINIFIED_LINE_LEN: usize = 500;
+
+/// Report `path` as skipped when it's over `max-file-size`, before reading it
+///
+/// Like binary files, explicitly listed files are always checked.
+fn report_too_large(
+    path: &std::path::Path,
+    explicit: bool,
+    policy: &crate::policy::Policy<'_, '_, '_>,
+    reporter: &dyn report::Report,
+) -> Result<bool, std::io::Error> {
+    let Some((len, max_file_size)) = too_large(path, explicit, policy) else {
+        return Ok(false);
+    };
+    let msg = report::Skipped {
+        context: Some(report::PathContext { path }.into()),
+        reason: format!("is {len} bytes, over `max-file-size` of {max_file_size}").into(),
+    };
+    reporter.report(msg.into())?;
+    Ok(true)
+}
+
+/// The size of `path` and `max-file-size`, when `path` is over it
+fn too_large(
+    path: &std::path::Path,
+    explicit: bool,
+    policy: &crate::policy::Policy<'_, '_, '_>,
+) -> Option<(u64, u64)> {
+    let max_file_size = policy.max_file_size.filter(|_| !explicit)?;
+    if path == std::path::Path::new("-") {
+        return None;
+    }
+    // Errors are reported when reading
+    let len = std::fs::metadata(path).ok()?.len();
+    (max_file_size < len).then_some((len, max_file_size))
+}
+
+/// Report why `path`, or lines in it, are skipped, returning whether all of it is
+///
+/// Files are skipped when `skip-generated` is set and they look generated or vendored, though
+/// like binary files, explicitly listed files are always checked.
+/// Lines are skipped when over `max-line-length`.
+fn report_skipped(
+    path: &std::path::Path,
+    explicit: bool,
+    buffer: &[u8],
+    policy: &crate::policy::Policy<'_, '_, '_>,
+    reporter: &dyn report::Report,
+) -> Result<bool, std::io::Error> {
+    if !explicit && policy.skip_generated {
+        if let Some(reason) = policy
+            .generated_attribute
+            .or_else(|| generated_reason(path, buffer, policy.file_type))
+        {
+            let msg = report::Skipped {
+                context: Some(report::PathContext { path }.into()),
+                reason: reason.into(),
+            };
+            reporter.report(msg.into())?;
+            return Ok(true);
+        }
+    }
+
+    if let Some(max_line_length) = policy.max_line_length {
+        for (line_num, line) in long_lines(buffer, max_line_length) {
+            if !is_changed_line(policy, line_num) {
+                continue;
+            }
+            let msg = report::Skipped {
+                context: Some(
+                    report::FileContext {
+                        path,
+                        line_num,
+                        line_start: line.start,
+                    }
+                    .into(),
+                ),
+                reason: format!(
+                    "line is {} bytes, over `max-line-length` of {max_line_length}",
+                    line.len()
+                )
+                .into(),
+            };
+            reporter.report(msg.into())?;
+        }
+    }
+
+    Ok(false)
+}
+
+/// 1-based line numbers and byte ranges, without line endings, of lines over `max_line_length`
+fn long_lines(
+    buffer: &[u8],
+    max_line_length: usize,
+) -> impl Iterator<Item = (usize, std::ops::Range<usize>)> + '_ {
+    let mut line_start = 0;
+    buffer
+        .lines_with_terminator()
+        .enumerate()
+        .filter_map(move |(i, line)| {
+            let start = line_start;
+            line_start += line.len();
+            let line = line.strip_suffix(b"\n").unwrap_or(line);
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            (max_line_length < line.len()).then(|| (i + 1, start..start + line.len()))
+        })
+}
+
+/// Why `path` looks generated, going by its name and the head of its contents
+pub(crate) fn generated_reason(
+    path: &std::path::Path,
+    buffer: &[u8],
+    file_type: Option<&str>,
+) -> Option<&'static str> {
+    let file_name = path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    if [".min.js", ".min.css"]
+        .iter()
+        .any(|suffix| file_name.ends_with(suffix))
+    {
+        return Some("is minified");
+    }
+    if LOCKFILE_NAMES.contains(&file_name) {
+        return Some("is a lockfile");
+    }
+
+    let minifiable = matches!(file_type, Some("js" | "css" | "json"));
+    for line in buffer.lines().take(GENERATED_HEAD_LINES) {
+        if let Some((_, reason)) = GENERATED_MARKERS
+            .iter()
+            .find(|(marker, _)| line.contains_str(marker))
+        {
+            return Some(reason);
+        }
+        if is_go_generated(line) {
+            return Some("has a `Code generated ... DO NOT EDIT` marker");
+        }
+        if minifiable && MINIFIED_LINE_LEN <= line.len() {
+            return Some("is minified");
+        }
+    }
+    None
+}
+
+/// Whether `line` is the marker Go tools leave, like `// Code generated by stringer. DO NOT EDIT.`
+///
+/// See <https://go.dev/s/generatedcode>
+fn is_go_generated(line: &[u8]) -> bool {
+    line.find("Code generated ")
+        .is_some_and(|start| line[start..].contains_str(" DO NOT EDIT"))
+}
+
 fn write_file(
     path: &std::path::Path,
     content_type: content_inspector::ContentType,
@@ -773,30 +1452,108 @@ fn write_file(
     Ok(())
 }
 
-fn check_str<'a>(
+/// Check a file name, which unlike file content has no markup or syntax to skip
+pub(crate) fn check_str<'a>(
     buffer: &'a str,
     policy: &'a crate::policy::Policy<'a, 'a, 'a>,
+    ignores: &'a mut Option<Ignores>,
+    fix: bool,
 ) -> impl Iterator<Item = typos::Typo<'a>> {
-    let mut ignores: Option<Ignores> = None;
-
-    typos::check_str(buffer, policy.tokenizer, policy.dict).filter(move |typo| {
-        !ignores
-            .get_or_insert_with(|| Ignores::new(buffer.as_bytes(), policy.ignore))
-            .is_ignored(typo.span())
-    })
+    let mut counts = None;
+    typos::check_str(buffer, policy.tokenizer, policy.dict)
+        .filter(move |typo| {
+            !ignores
+                .get_or_insert_with(|| Ignores::new(buffer.as_bytes(), policy.ignore))
+                .is_ignored(typo.span(), &typo.typo)
+        })
+        .map(move |typo| rank(typo, buffer.as_bytes(), policy, &mut counts, fix))
 }
 
-fn check_bytes<'a>(
+/// Check an in-memory `buffer`, respecting `policy.ignore` and inline directives
+///
+/// `ignores` is filled in once a typo is found, to later tell which directives were unused.
+pub fn check_bytes<'a>(
     buffer: &'a [u8],
     policy: &'a crate::policy::Policy<'a, 'a, 'a>,
+    ignores: &'a mut Option<Ignores>,
+    fix: bool,
 ) -> impl Iterator<Item = typos::Typo<'a>> {
-    let mut ignores: Option<Ignores> = None;
+    let mut counts = None;
+    typos::check_bytes(buffer, policy.tokenizer, policy.dict)
+        .filter(move |typo| {
+            !ignores
+                .get_or_insert_with(|| Ignores::for_buffer(buffer, policy))
+                .is_ignored(typo.span(), &typo.typo)
+        })
+        .map(move |typo| rank(typo, buffer, policy, &mut counts, fix))
+}
 
-    typos::check_bytes(buffer, policy.tokenizer, policy.dict).filter(move |typo| {
-        !ignores
-            .get_or_insert_with(|| Ignores::new(buffer, policy.ignore))
-            .is_ignored(typo.span())
-    })
+/// Order corrections per `policy.rank_corrections`, keeping only the best when `fix`ing and it
+/// is clear
+fn rank<'t, 'b>(
+    mut typo: typos::Typo<'t>,
+    buffer: &'b [u8],
+    policy: &crate::policy::Policy<'_, '_, '_>,
+    counts: &mut Option<crate::dict::WordCounts<'b>>,
+    fix: bool,
+) -> typos::Typo<'t> {
+    if !policy.rank_corrections {
+        return typo;
+    }
+    if let typos::Status::Corrections(corrections) = &mut typo.corrections {
+        if 1 < corrections.len() {
+            let counts = counts.get_or_insert_with(|| crate::dict::WordCounts::from_buffer(buffer));
+            let confident =
+                crate::dict::rank_corrections(&typo.typo, corrections, counts, policy.word_counts);
+            if fix && confident {
+                corrections.truncate(1);
+            }
+        }
+    }
+    typo
+}
+
+fn is_changed_filename(policy: &crate::policy::Policy<'_, '_, '_>) -> bool {
+    policy
+        .changes
+        .is_none_or(|changes| changes.filename_changed())
+}
+
+fn is_changed_line(policy: &crate::policy::Policy<'_, '_, '_>, line_num: usize) -> bool {
+    policy
+        .changes
+        .is_none_or(|changes| changes.contains_line(line_num))
+}
+
+fn report_unused_directives(
+    path: &std::path::Path,
+    buffer: &[u8],
+    ignores: Option<Ignores>,
+    reporter: &dyn report::Report,
+) -> Result<(), std::io::Error> {
+    // `Ignores` is only built once a typo is found, so without it every directive is unused
+    let directives = ignores
+        .map(|ignores| ignores.directives)
+        .unwrap_or_else(|| Directive::parse(buffer));
+    for directive in directives.iter().filter(|d| !d.used.get()) {
+        let line_start = buffer[..directive.span.start]
+            .rfind_byte(b'\n')
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        let msg = report::UnusedDirective {
+            context: Some(
+                report::FileContext {
+                    path,
+                    line_num: directive.line_num,
+                    line_start,
+                }
+                .into(),
+            ),
+            directive: directive.kind.to_string().into(),
+        };
+        reporter.report(msg.into())?;
+    }
+    Ok(())
 }
 
 fn report_result<T: Default, E: ToString>(
@@ -825,13 +1582,13 @@ fn report_error<E: ToString>(
     Ok(())
 }
 
-struct AccumulateLineNum {
+pub(crate) struct AccumulateLineNum {
     line_num: usize,
     last_offset: usize,
 }
 
 impl AccumulateLineNum {
-    fn new() -> Self {
+    pub(crate) fn new() -> Self {
         Self {
             // 1-indexed
             line_num: 1,
@@ -839,7 +1596,7 @@ impl AccumulateLineNum {
         }
     }
 
-    fn line_num(&mut self, buffer: &[u8], byte_offset: usize) -> usize {
+    pub(crate) fn line_num(&mut self, buffer: &[u8], byte_offset: usize) -> usize {
         assert!(self.last_offset <= byte_offset);
         let slice = &buffer[self.last_offset..byte_offset];
         let newlines = slice.find_iter(b"\n").count();
@@ -850,7 +1607,7 @@ impl AccumulateLineNum {
     }
 }
 
-fn extract_line(buffer: &[u8], byte_offset: usize) -> (&[u8], usize) {
+pub(crate) fn extract_line(buffer: &[u8], byte_offset: usize) -> (&[u8], usize) {
     let line_start = buffer[0..byte_offset]
         .rfind_byte(b'\n')
         // Skip the newline
@@ -871,11 +1628,23 @@ fn extract_fix<'t>(typo: &'t typos::Typo<'t>) -> Option<&'t str> {
     }
 }
 
-fn is_fixable(typo: &typos::Typo<'_>) -> bool {
+pub(crate) fn is_preferred(
+    policy: &crate::policy::Policy<'_, '_, '_>,
+    typo: &typos::Typo<'_>,
+) -> bool {
+    policy
+        .preferred_corrections
+        .is_some_and(|preferred| preferred.is_preferred(&typo.typo, &typo.corrections))
+}
+
+pub(crate) fn is_fixable(typo: &typos::Typo<'_>) -> bool {
     extract_fix(typo).is_some()
 }
 
-fn fix_buffer(mut buffer: Vec<u8>, typos: impl Iterator<Item = typos::Typo<'static>>) -> Vec<u8> {
+pub(crate) fn fix_buffer(
+    mut buffer: Vec<u8>,
+    typos: impl Iterator<Item = typos::Typo<'static>>,
+) -> Vec<u8> {
     let mut offset = 0isize;
     for typo in typos {
         let fix = extract_fix(&typo).expect("Caller only provides fixable typos");
@@ -890,7 +1659,7 @@ fn fix_buffer(mut buffer: Vec<u8>, typos: impl Iterator<Item = typos::Typo<'stat
 }
 
 pub fn walk_path(
-    walk: ignore::Walk,
+    walk: impl IntoIterator<Item = Result<ignore::DirEntry, ignore::Error>>,
     checks: &dyn FileChecker,
     engine: &crate::policy::ConfigEngine<'_>,
     reporter: &dyn report::Report,
@@ -925,6 +1694,49 @@ pub fn walk_path_parallel(
     error.into_inner().unwrap()
 }
 
+/// Count the words in the files of `walk` that have `rank-corrections` set, for
+/// [`crate::policy::ConfigEngine::set_word_counts`]
+///
+/// Files are counted on a best-effort basis, skipping those that can't be read, are binary, or are
+/// over `max-file-size`.
+pub fn count_words(
+    walk: ignore::WalkParallel,
+    engine: &crate::policy::ConfigEngine<'_>,
+) -> crate::dict::RepoWordCounts {
+    let counts = std::sync::Mutex::new(crate::dict::RepoWordCounts::default());
+    walk.run(|| {
+        Box::new(|entry: Result<ignore::DirEntry, ignore::Error>| {
+            let Ok(entry) = entry else {
+                return ignore::WalkState::Continue;
+            };
+            if entry.is_stdin() || !entry.file_type().is_some_and(|t| t.is_file()) {
+                return ignore::WalkState::Continue;
+            }
+            let path = entry.path();
+            let Ok(abs_path) = path.canonicalize() else {
+                return ignore::WalkState::Continue;
+            };
+            let policy = engine.policy(&abs_path);
+            if !policy.rank_corrections
+                || !policy.check_files
+                || engine.is_dict_file(&abs_path)
+                || too_large(path, false, &policy).is_some()
+            {
+                return ignore::WalkState::Continue;
+            }
+            let Ok(buffer) = std::fs::read(path) else {
+                return ignore::WalkState::Continue;
+            };
+            if policy.binary || !content_inspector::inspect(&buffer).is_binary() {
+                let words = crate::dict::WordCounts::from_buffer(&buffer);
+                counts.lock().unwrap().add(&words);
+            }
+            ignore::WalkState::Continue
+        })
+    });
+    counts.into_inner().unwrap()
+}
+
 fn walk_entry(
     entry: Result<ignore::DirEntry, ignore::Error>,
     checks: &dyn FileChecker,
@@ -961,6 +1773,17 @@ fn walk_entry(
         } else {
             let path = entry.path();
             let abs_path = report_result(path.canonicalize(), Some(path), reporter)?;
+            if !engine.is_changed(&abs_path) {
+                log::debug!("{}: skipping unchanged file", path.display());
+                return Ok(());
+            }
+            if engine.is_dict_file(&abs_path) {
+                log::debug!(
+                    "{}: skipping dictionary file as it may have typos",
+                    path.display()
+                );
+                return Ok(());
+            }
             (path, abs_path)
         };
         let policy = engine.policy(&lookup_path);
@@ -970,9 +1793,11 @@ fn walk_entry(
     Ok(())
 }
 
+/// What is ignored within a buffer, see [`check_bytes`]
 #[derive(Clone, Debug)]
-struct Ignores {
+pub struct Ignores {
     blocks: Vec<std::ops::Range<usize>>,
+    directives: Vec<Directive>,
 }
 
 impl Ignores {
@@ -985,18 +1810,176 @@ impl Ignores {
                 }
             }
         }
-        Self { blocks }
+        let directives = Directive::parse(content);
+        Self { blocks, directives }
+    }
+
+    /// Also ignore regions of file content excluded by `policy.markup` and `policy.regions`
+    fn for_buffer(content: &[u8], policy: &crate::policy::Policy<'_, '_, '_>) -> Self {
+        let mut ignores = Self::new(content, policy.ignore);
+        if policy.markup {
+            match policy.file_type {
+                Some("md") => ignores
+                    .blocks
+                    .extend(crate::markup::markdown(content, policy.code_blocks)),
+                Some("rst") => ignores
+                    .blocks
+                    .extend(crate::markup::rst(content, policy.code_blocks)),
+                _ => {}
+            }
+        }
+        if let Some(max_line_length) = policy.max_line_length {
+            ignores
+                .blocks
+                .extend(long_lines(content, max_line_length).map(|(_, line)| line));
+        }
+        if !policy.regions.is_all() {
+            if let Some(syntax) = policy.file_type.and_then(crate::syntax::Syntax::for_type) {
+                ignores
+                    .blocks
+                    .extend(syntax.excluded(content, policy.regions));
+            }
+        }
+        ignores
     }
 
-    fn is_ignored(&self, span: std::ops::Range<usize>) -> bool {
+    fn is_ignored(&self, span: std::ops::Range<usize>, token: &str) -> bool {
         let start = span.start;
         let end = span.end.saturating_sub(1);
-        self.blocks
-            .iter()
-            .any(|block| block.contains(&start) || block.contains(&end))
+        let mut ignored = false;
+        // Check every directive so each one that applies gets marked as used
+        for directive in self.directives.iter() {
+            if directive.span.contains(&start) {
+                // Words listed in the directive itself
+                ignored = true;
+            } else if directive.suppresses(start, end, token) {
+                directive.used.set(true);
+                ignored = true;
+            }
+        }
+        ignored
+            || self
+                .blocks
+                .iter()
+                .any(|block| block.contains(&start) || block.contains(&end))
     }
 }
 
+/// Inline `typos: <kind>` comment, suppressing typos in its region
+#[derive(Clone, Debug)]
+struct Directive {
+    kind: DirectiveKind,
+    line_num: usize,
+    span: std::ops::Range<usize>,
+    region: std::ops::Range<usize>,
+    /// Only suppress these words, rather than all typos in the region
+    words: Vec<String>,
+    used: std::cell::Cell<bool>,
+}
+
+impl Directive {
+    fn parse(content: &[u8]) -> Vec<Self> {
+        let mut directives = Vec::new();
+        if content.find(b"typos:").is_none() {
+            return directives;
+        }
+
+        let mut accum_line_num = AccumulateLineNum::new();
+        let mut open: Option<Directive> = None;
+        for captures in DIRECTIVE.captures_iter(content) {
+            let mat = captures.get(0).expect("always present");
+            let line_num = accum_line_num.line_num(content, mat.start());
+            let line_end = content[mat.end()..]
+                .find_byte(b'\n')
+                .map(|i| mat.end() + i)
+                .unwrap_or(content.len());
+            let words = captures
+                .get(2)
+                .map(|words| {
+                    words
+                        .as_bytes()
+                        .split(|b| *b == b',')
+                        .map(|w| String::from_utf8_lossy(w).into_owned())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let (kind, region) = match &captures[1] {
+                b"ignore-line" => {
+                    let line_start = content[..mat.start()]
+                        .rfind_byte(b'\n')
+                        .map(|i| i + 1)
+                        .unwrap_or(0);
+                    (DirectiveKind::IgnoreLine, line_start..line_end)
+                }
+                b"ignore-next-line" => {
+                    let next_start = (line_end + 1).min(content.len());
+                    let next_end = content[next_start..]
+                        .find_byte(b'\n')
+                        .map(|i| next_start + i)
+                        .unwrap_or(content.len());
+                    (DirectiveKind::IgnoreNextLine, next_start..next_end)
+                }
+                b"off" => {
+                    if let Some(mut prev) = open.take() {
+                        prev.region.end = mat.start();
+                        directives.push(prev);
+                    }
+                    open = Some(Directive {
+                        kind: DirectiveKind::Off,
+                        line_num,
+                        span: mat.range(),
+                        region: mat.end()..content.len(),
+                        words,
+                        used: Default::default(),
+                    });
+                    continue;
+                }
+                b"on" => {
+                    if let Some(mut prev) = open.take() {
+                        prev.region.end = mat.start();
+                        directives.push(prev);
+                    }
+                    continue;
+                }
+                _ => unreachable!("regex only matches known directives"),
+            };
+            directives.push(Directive {
+                kind,
+                line_num,
+                span: mat.range(),
+                region,
+                words,
+                used: Default::default(),
+            });
+        }
+        directives.extend(open);
+
+        directives
+    }
+
+    fn suppresses(&self, start: usize, end: usize, token: &str) -> bool {
+        (self.region.contains(&start) || self.region.contains(&end))
+            && (self.words.is_empty() || self.words.iter().any(|w| unicase::eq(w.as_str(), token)))
+    }
+}
+
+static DIRECTIVE: std::sync::LazyLock<regex::bytes::Regex> = std::sync::LazyLock::new(|| {
+    regex::bytes::Regex::new(
+        r"\btypos:[ \t]*(ignore-next-line|ignore-line|off|on)\b(?:=([\w'-]+(?:,[\w'-]+)*))?",
+    )
+    .unwrap()
+});
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, derive_more::Display)]
+enum DirectiveKind {
+    #[display("ignore-line")]
+    IgnoreLine,
+    #[display("ignore-next-line")]
+    IgnoreNextLine,
+    #[display("off")]
+    Off,
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1084,6 +2067,14 @@ mod test {
         assert_eq!(line_num, 3);
     }
 
+    #[test]
+    fn test_long_lines() {
+        let buffer = b"short\nlonger line\r\nshort\nlonger line";
+        let lines: Vec<_> = long_lines(buffer, 5).collect();
+        assert_eq!(lines, vec![(2, 6..17), (4, 25..36)]);
+        assert_eq!(long_lines(buffer, 11).count(), 0);
+    }
+
     #[test]
     fn test_extract_line_single_line() {
         let buffer = b"hello world";
@@ -1160,4 +2151,119 @@ mod test {
         assert_eq!(offset, 28);
         assert_eq!(line[offset], buffer[buffer_offset]);
     }
+
+    fn ignored_words(content: &str) -> Vec<&str> {
+        let ignores = Ignores::new(content.as_bytes(), &[]);
+        static TOKENIZER: typos::tokens::Tokenizer = typos::tokens::Tokenizer::new();
+        TOKENIZER
+            .parse_str(content)
+            .flat_map(|i| i.split())
+            .filter(|word| ignores.is_ignored(word.span(), word.token()))
+            .map(|word| word.token())
+            .filter(|word| !["typos", "ignore", "next", "line", "off", "on"].contains(word))
+            .collect()
+    }
+
+    #[test]
+    fn test_directive_ignore_line() {
+        let actual = ignored_words("hello\nworld // typos: ignore-line\ngoodbye");
+        assert_eq!(actual, ["world"]);
+    }
+
+    #[test]
+    fn test_directive_ignore_next_line() {
+        let actual = ignored_words("hello\n# typos: ignore-next-line\nworld\ngoodbye");
+        assert_eq!(actual, ["world"]);
+    }
+
+    #[test]
+    fn test_directive_ignore_next_line_at_eof() {
+        let actual = ignored_words("hello\n# typos: ignore-next-line");
+        assert_eq!(actual, Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_directive_off_on() {
+        let actual =
+            ignored_words("hello\n<!-- typos: off -->\nworld\n<!-- typos: on -->\ngoodbye");
+        assert_eq!(actual, ["world"]);
+    }
+
+    #[test]
+    fn test_directive_off_to_eof() {
+        let actual = ignored_words("hello\n/* typos: off */\nworld\ngoodbye");
+        assert_eq!(actual, ["world", "goodbye"]);
+    }
+
+    #[test]
+    fn test_directive_words() {
+        let actual = ignored_words("hello World // typos: ignore-line=world,goodbye");
+        assert_eq!(actual, ["World", "world", "goodbye"]);
+    }
+
+    #[test]
+    fn test_directive_unused() {
+        let content = b"hello\n# typos: ignore-next-line\nworld\n# typos: ignore-line";
+        let ignores = Ignores::new(content, &[]);
+        assert!(ignores.is_ignored(32..37, "world"));
+        let unused: Vec<_> = ignores
+            .directives
+            .iter()
+            .filter(|d| !d.used.get())
+            .map(|d| (d.kind, d.line_num))
+            .collect();
+        assert_eq!(unused, [(DirectiveKind::IgnoreLine, 4)]);
+    }
+    #[derive(Default)]
+    struct CollectTypos(std::sync::Mutex<Vec<String>>);
+
+    impl report::Report for CollectTypos {
+        fn report(&self, msg: report::Message<'_>) -> Result<(), std::io::Error> {
+            if let report::Message::Typo(typo) = msg {
+                self.0.lock().unwrap().push(typo.typo.to_owned());
+            }
+            Ok(())
+        }
+    }
+
+    struct NoTypos;
+
+    impl typos::Dictionary for NoTypos {
+        fn correct_ident<'s>(
+            &'s self,
+            _ident: typos::tokens::Identifier<'_>,
+        ) -> Option<typos::Status<'s>> {
+            None
+        }
+
+        fn correct_word<'s>(&'s self, _word: typos::tokens::Word<'_>) -> Option<typos::Status<'s>> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_cache_replays_until_invalidated() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let path = temp.path().join("file.txt");
+        std::fs::write(&path, "teh\n").unwrap();
+        let cache = Cache::new(temp.path().join("cache")).unwrap();
+        let checks = CachedTypos { cache: &cache };
+        let check = |policy: crate::policy::Policy<'_, '_, '_>| {
+            let reporter = CollectTypos::default();
+            checks.check_file(&path, true, &policy, &reporter).unwrap();
+            reporter.0.into_inner().unwrap()
+        };
+
+        let policy = || crate::policy::Policy::new().fingerprint(Some(1));
+        assert_eq!(check(policy()), ["teh"]);
+        // Unchanged settings, so the dictionary isn't consulted
+        assert_eq!(check(policy().dict(&NoTypos)), ["teh"]);
+        assert_eq!(
+            check(policy().dict(&NoTypos).fingerprint(Some(2))),
+            Vec::<String>::new()
+        );
+
+        std::fs::write(&path, "the\n").unwrap();
+        assert_eq!(check(policy()), Vec::<String>::new());
+    }
 }
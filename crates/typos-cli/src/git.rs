@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Files and lines that differ from a git revision
+#[derive(Clone, Debug, Default)]
+pub struct Changes {
+    files: HashMap<PathBuf, FileChanges>,
+}
+
+impl Changes {
+    /// Compare the working tree containing `cwd` against `rev`
+    ///
+    /// Untracked files (that aren't ignored) are treated as entirely new.
+    pub fn from_rev(cwd: &Path, rev: &str) -> Result<Self, anyhow::Error> {
+        let root = git(cwd, &["rev-parse", "--show-toplevel"])?;
+        let root = PathBuf::from(root.trim_end_matches(['\n', '\r']));
+        let diff = git(
+            &root,
+            &[
+                "-c",
+                "core.quotePath=false",
+                "diff",
+                "--no-color",
+                "--no-ext-diff",
+                "--find-renames",
+                "--unified=0",
+                rev,
+                "--",
+            ],
+        )?;
+        let untracked = git(
+            &root,
+            &[
+                "-c",
+                "core.quotePath=false",
+                "ls-files",
+                "-z",
+                "--others",
+                "--exclude-standard",
+            ],
+        )?;
+
+        let mut changes = Self::from_diff(&root, &diff);
+        for path in untracked.split('\0').filter(|path| !path.is_empty()) {
+            changes
+                .files
+                .insert(root.join(path), FileChanges::new_file());
+        }
+        Ok(changes)
+    }
+
+    /// Parse the output of `git diff --unified=0`, relative to `root`
+    pub fn from_diff(root: &Path, diff: &str) -> Self {
+        let mut files = HashMap::new();
+        let mut current: Option<(PathBuf, FileChanges)> = None;
+        for line in diff.lines() {
+            if line.starts_with("diff --git ") {
+                files.extend(current.take());
+                current = Some((PathBuf::new(), FileChanges::default()));
+                continue;
+            }
+            let Some((path, changes)) = current.as_mut() else {
+                continue;
+            };
+            if line.starts_with("new file mode") || line.starts_with("rename to ") {
+                changes.filename = true;
+            } else if let Some(new_path) = line.strip_prefix("+++ ") {
+                if let Some(new_path) = unquote(new_path).strip_prefix("b/") {
+                    *path = root.join(new_path);
+                } else {
+                    // Deleted
+                    current = None;
+                }
+            } else if let Some(hunk) = line.strip_prefix("@@ ") {
+                if let Some(lines) = parse_hunk(hunk) {
+                    changes.lines.push(lines);
+                }
+            }
+        }
+        files.extend(current);
+        files.retain(|path: &PathBuf, _| !path.as_os_str().is_empty());
+
+        Self { files }
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&FileChanges> {
+        self.files.get(path)
+    }
+
+    pub fn contains_file(&self, path: &Path) -> bool {
+        self.files.contains_key(path)
+    }
+}
+
+/// Lines that differ within a file
+#[derive(Clone, Debug, Default)]
+pub struct FileChanges {
+    filename: bool,
+    /// 1-indexed line numbers
+    lines: Vec<std::ops::RangeInclusive<usize>>,
+}
+
+impl FileChanges {
+    fn new_file() -> Self {
+        Self {
+            filename: true,
+            lines: vec![1..=usize::MAX],
+        }
+    }
+
+    /// Whether the file is new or renamed
+    pub fn filename_changed(&self) -> bool {
+        self.filename
+    }
+
+    pub fn contains_line(&self, line_num: usize) -> bool {
+        self.lines.iter().any(|lines| lines.contains(&line_num))
+    }
+}
+
+/// Parse the added lines from `-l,s +l,s @@`
+fn parse_hunk(hunk: &str) -> Option<std::ops::RangeInclusive<usize>> {
+    let added = hunk.split(' ').find_map(|s| s.strip_prefix('+'))?;
+    let (start, len) = match added.split_once(',') {
+        Some((start, len)) => (start.parse::<usize>().ok()?, len.parse::<usize>().ok()?),
+        None => (added.parse::<usize>().ok()?, 1),
+    };
+    if len == 0 {
+        // Only removals
+        return None;
+    }
+    Some(start..=start + len - 1)
+}
+
+/// Decode a path from a diff header, which git quotes with C-style escapes when it has special
+/// characters
+fn unquote(path: &str) -> std::borrow::Cow<'_, str> {
+    // git appends a tab to paths with spaces
+    let path = path.trim_end_matches('\t');
+    let Some(quoted) = path.strip_prefix('"').and_then(|p| p.strip_suffix('"')) else {
+        return path.into();
+    };
+
+    let mut bytes = Vec::with_capacity(quoted.len());
+    let mut chars = quoted.bytes();
+    while let Some(b) = chars.next() {
+        if b != b'\\' {
+            bytes.push(b);
+            continue;
+        }
+        let Some(escaped) = chars.next() else {
+            break;
+        };
+        let b = match escaped {
+            b'a' => b'\x07',
+            b'b' => b'\x08',
+            b't' => b'\t',
+            b'n' => b'\n',
+            b'v' => b'\x0b',
+            b'f' => b'\x0c',
+            b'r' => b'\r',
+            // Octal bytes, like for non-ASCII characters with `core.quotePath`
+            b'0'..=b'3' => {
+                let mut value = escaped - b'0';
+                for _ in 0..2 {
+                    match chars.clone().next() {
+                        Some(digit @ b'0'..=b'7') => {
+                            value = value * 8 + (digit - b'0');
+                            chars.next();
+                        }
+                        _ => break,
+                    }
+                }
+                value
+            }
+            escaped => escaped,
+        };
+        bytes.push(b);
+    }
+    String::from_utf8_lossy(&bytes).into_owned().into()
+}
+
+fn git(cwd: &Path, args: &[&str]) -> Result<String, anyhow::Error> {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .map_err(|err| anyhow::format_err!("could not run `git`: {err}"))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let stdout = String::from_utf8(output.stdout)
+        .map_err(|_| anyhow::format_err!("`git {}` output is not UTF-8", args.join(" ")))?;
+    Ok(stdout)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3,0 +4,2 @@ fn foo() {
++    let a = 1;
++    let b = 2;
+@@ -10 +12 @@ fn bar() {
+-    old
++    new
+@@ -20,3 +21,0 @@ fn baz() {
+-    a
+-    b
+-    c
+diff --git a/new.md b/new.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/new.md
+@@ -0,0 +1,3 @@
++one
++two
++three
+diff --git a/removed.md b/removed.md
+deleted file mode 100644
+index 3333333..0000000
+--- a/removed.md
++++ /dev/null
+@@ -1 +0,0 @@
+-one
+diff --git a/old name.md b/new name.md
+similarity index 90%
+rename from old name.md
+rename to new name.md
+--- a/old name.md\t
++++ b/new name.md\t
+@@ -1 +1 @@
+-one
++uno
+";
+
+    #[test]
+    fn parse_modified() {
+        let root = Path::new("/repo");
+        let changes = Changes::from_diff(root, DIFF);
+        let file = changes.get(&root.join("src/lib.rs")).unwrap();
+        assert!(!file.filename_changed());
+        assert!(!file.contains_line(3));
+        assert!(file.contains_line(4));
+        assert!(file.contains_line(5));
+        assert!(!file.contains_line(6));
+        assert!(file.contains_line(12));
+        assert!(!file.contains_line(21));
+    }
+
+    #[test]
+    fn parse_new() {
+        let root = Path::new("/repo");
+        let changes = Changes::from_diff(root, DIFF);
+        let file = changes.get(&root.join("new.md")).unwrap();
+        assert!(file.filename_changed());
+        assert!(file.contains_line(1));
+        assert!(file.contains_line(3));
+        assert!(!file.contains_line(4));
+    }
+
+    #[test]
+    fn parse_deleted() {
+        let root = Path::new("/repo");
+        let changes = Changes::from_diff(root, DIFF);
+        assert!(!changes.contains_file(&root.join("removed.md")));
+    }
+
+    #[test]
+    fn parse_quoted() {
+        let diff = r#"diff --git "a/caf\303\251.md" "b/caf\303\251.md"
+index 1111111..2222222 100644
+--- "a/caf\303\251.md"
++++ "b/caf\303\251.md"
+@@ -1 +1 @@
+-one
++uno
+diff --git "a/say \"hi\"\\tab.md" "b/say \"hi\"\\tab.md"
+index 1111111..2222222 100644
+--- "a/say \"hi\"\\tab.md"
++++ "b/say \"hi\"\\tab.md"	
+@@ -1 +1 @@
+-one
++uno
+"#;
+        let root = Path::new("/repo");
+        let changes = Changes::from_diff(root, diff);
+        assert!(changes.contains_file(&root.join("café.md")));
+        assert!(changes.contains_file(&root.join(r#"say "hi"\tab.md"#)));
+    }
+
+    #[test]
+    fn parse_renamed() {
+        let root = Path::new("/repo");
+        let changes = Changes::from_diff(root, DIFF);
+        assert!(!changes.contains_file(&root.join("old name.md")));
+        let file = changes.get(&root.join("new name.md")).unwrap();
+        assert!(file.filename_changed());
+        assert!(file.contains_line(1));
+    }
+}
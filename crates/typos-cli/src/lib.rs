@@ -5,6 +5,7 @@
 #![warn(clippy::print_stderr)]
 #![warn(clippy::print_stdout)]
 
+pub mod checker;
 #[doc(hidden)]
 pub mod config;
 #[doc(hidden)]
@@ -12,10 +13,16 @@ pub mod dict;
 #[doc(hidden)]
 pub mod file;
 #[doc(hidden)]
+pub mod git;
+#[doc(hidden)]
 pub mod policy;
 #[doc(hidden)]
 pub mod report;
+#[doc(hidden)]
+pub mod syntax;
 
+mod attributes;
 mod default_types;
 mod file_type;
 mod file_type_specifics;
+mod markup;
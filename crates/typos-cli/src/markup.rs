@@ -0,0 +1,384 @@
+//! Structural pre-pass for markup file types, finding regions that aren't prose
+//!
+//! Like [`crate::syntax`], this is not a full parser.
+
+use std::ops::Range;
+
+use bstr::ByteSlice;
+
+use crate::config::CodeBlocks;
+use crate::syntax::{Regions, Syntax};
+
+/// Byte ranges of Markdown `content` that aren't prose
+///
+/// This covers code spans, link targets, HTML tags, and fenced code blocks (per `code_blocks`).
+pub(crate) fn markdown(content: &[u8], code_blocks: CodeBlocks) -> Vec<Range<usize>> {
+    let mut excluded = Vec::new();
+    let mut text_start = 0;
+    let mut fence: Option<Fence<'_>> = None;
+    for (line_start, line) in lines(content) {
+        let line_end = line_start + line.len();
+        match fence.as_ref() {
+            Some(open) => {
+                if open.is_closed_by(line) {
+                    code_block(
+                        content,
+                        open.content_start..line_start,
+                        open.info,
+                        code_blocks,
+                        &mut excluded,
+                    );
+                    excluded.push(line_start..line_end);
+                    fence = None;
+                    text_start = line_end;
+                }
+            }
+            None => {
+                if let Some(mut open) = Fence::open(line) {
+                    markdown_text(content, text_start..line_start, &mut excluded);
+                    excluded.push(line_start..line_end);
+                    open.content_start = line_end;
+                    fence = Some(open);
+                }
+            }
+        }
+    }
+    match fence {
+        // Unclosed fences run to the end of the document
+        Some(open) => code_block(
+            content,
+            open.content_start..content.len(),
+            open.info,
+            code_blocks,
+            &mut excluded,
+        ),
+        None => markdown_text(content, text_start..content.len(), &mut excluded),
+    }
+    excluded
+}
+
+struct Fence<'c> {
+    marker: u8,
+    len: usize,
+    info: &'c [u8],
+    content_start: usize,
+}
+
+impl<'c> Fence<'c> {
+    fn open(line: &'c [u8]) -> Option<Self> {
+        let line = strip_indent(line, 3)?;
+        let marker = *line.first()?;
+        if marker != b'`' && marker != b'~' {
+            return None;
+        }
+        let len = line.iter().take_while(|b| **b == marker).count();
+        if len < 3 {
+            return None;
+        }
+        let info = line[len..].trim();
+        if marker == b'`' && info.contains(&b'`') {
+            return None;
+        }
+        Some(Self {
+            marker,
+            len,
+            info,
+            content_start: 0,
+        })
+    }
+
+    fn is_closed_by(&self, line: &[u8]) -> bool {
+        let Some(line) = strip_indent(line, 3) else {
+            return false;
+        };
+        let len = line.iter().take_while(|b| **b == self.marker).count();
+        self.len <= len && line[len..].trim().is_empty()
+    }
+}
+
+/// Inline constructs, outside of code blocks
+fn markdown_text(content: &[u8], text: Range<usize>, excluded: &mut Vec<Range<usize>>) {
+    let offset = text.start;
+    let text = &content[text];
+    for captures in MARKDOWN_REFERENCE.captures_iter(text) {
+        let target = captures.get(1).expect("always present").range();
+        excluded.push(offset + target.start..offset + target.end);
+    }
+
+    let mut i = 0;
+    while i < text.len() {
+        match text[i] {
+            b'\\' => {
+                i += 2;
+            }
+            b'`' => {
+                let len = text[i..].iter().take_while(|b| **b == b'`').count();
+                match code_span_end(text, i + len, len) {
+                    Some(end) => {
+                        excluded.push(offset + i..offset + end);
+                        i = end;
+                    }
+                    None => i += len,
+                }
+            }
+            b']' if text.get(i + 1) == Some(&b'(') => {
+                let start = i + 2;
+                let mut depth = 1;
+                let mut end = start;
+                while end < text.len() && text[end] != b'\n' {
+                    match text[end] {
+                        b'(' => depth += 1,
+                        b')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    end += 1;
+                }
+                excluded.push(offset + start..offset + end);
+                i = end;
+            }
+            b'<' if text[i..].starts_with(b"<!--") => {
+                // HTML comments are prose
+                i += 4;
+            }
+            b'<' if text
+                .get(i + 1)
+                .is_some_and(|b| b.is_ascii_alphabetic() || *b == b'/') =>
+            {
+                match text[i..].iter().position(|b| *b == b'>' || *b == b'\n') {
+                    Some(len) if text[i + len] == b'>' => {
+                        excluded.push(offset + i..offset + i + len + 1);
+                        i += len + 1;
+                    }
+                    _ => i += 1,
+                }
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// Find the closing backtick run of exactly `len`, within the paragraph
+fn code_span_end(text: &[u8], start: usize, len: usize) -> Option<usize> {
+    let mut i = start;
+    while i < text.len() {
+        if text[i..].starts_with(b"\n\n") {
+            return None;
+        }
+        if text[i] == b'`' {
+            let run = text[i..].iter().take_while(|b| **b == b'`').count();
+            if run == len {
+                return Some(i + run);
+            }
+            i += run;
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+static MARKDOWN_REFERENCE: std::sync::LazyLock<regex::bytes::Regex> =
+    std::sync::LazyLock::new(|| {
+        regex::bytes::Regex::new(r"(?m)^ {0,3}\[[^\]\n]+\]:[ \t]*(\S+)").unwrap()
+    });
+
+/// Byte ranges of reStructuredText `content` that aren't prose
+///
+/// This covers inline literals, link targets, and literal blocks (per `code_blocks`).
+pub(crate) fn rst(content: &[u8], code_blocks: CodeBlocks) -> Vec<Range<usize>> {
+    let mut excluded = Vec::new();
+    for captures in RST_TARGET.captures_iter(content) {
+        excluded.push(captures.get(1).expect("always present").range());
+    }
+    for mat in RST_INLINE.find_iter(content) {
+        excluded.push(mat.range());
+    }
+
+    let lines: Vec<_> = lines(content).collect();
+    let mut i = 0;
+    while i < lines.len() {
+        let (_, line) = lines[i];
+        i += 1;
+        let trimmed = line.trim();
+        let info: &[u8] = if let Some(captures) = RST_CODE_DIRECTIVE.captures(line) {
+            captures.get(1).map(|m| m.as_bytes()).unwrap_or_default()
+        } else if trimmed.ends_with(b"::") && !trimmed.starts_with(b"..") {
+            b""
+        } else {
+            continue;
+        };
+
+        // The block is everything indented further than the line introducing it
+        let block_indent = indent(line);
+        let mut block: Option<Range<usize>> = None;
+        while i < lines.len() {
+            let (start, line) = lines[i];
+            if line.trim().is_empty() {
+                i += 1;
+                continue;
+            }
+            if indent(line) <= block_indent {
+                break;
+            }
+            let end = start + line.len();
+            block = Some(block.map(|block| block.start..end).unwrap_or(start..end));
+            i += 1;
+        }
+        if let Some(block) = block {
+            code_block(content, block, info, code_blocks, &mut excluded);
+        }
+    }
+    excluded
+}
+
+static RST_TARGET: std::sync::LazyLock<regex::bytes::Regex> = std::sync::LazyLock::new(|| {
+    regex::bytes::Regex::new(r"(?m)^[ \t]*\.\. _[^:\n]+:[ \t]*(\S+)").unwrap()
+});
+static RST_INLINE: std::sync::LazyLock<regex::bytes::Regex> = std::sync::LazyLock::new(|| {
+    // Inline literals and embedded link targets
+    regex::bytes::Regex::new(r"``[^`]+``|<[^<>\s][^<>\n]*>`__?").unwrap()
+});
+static RST_CODE_DIRECTIVE: std::sync::LazyLock<regex::bytes::Regex> =
+    std::sync::LazyLock::new(|| {
+        regex::bytes::Regex::new(r"^[ \t]*\.\. (?:code-block|code|sourcecode)::[ \t]*(\S*)")
+            .unwrap()
+    });
+
+fn code_block(
+    content: &[u8],
+    block: Range<usize>,
+    info: &[u8],
+    code_blocks: CodeBlocks,
+    excluded: &mut Vec<Range<usize>>,
+) {
+    match code_blocks {
+        CodeBlocks::Check => {}
+        CodeBlocks::Skip => excluded.push(block),
+        CodeBlocks::Language => {
+            let syntax = info
+                .split(|b| b.is_ascii_whitespace() || *b == b',' || *b == b'}')
+                .next()
+                .and_then(|lang| {
+                    std::str::from_utf8(lang.trim_start_with(|c| c == '{' || c == '.')).ok()
+                })
+                .and_then(Syntax::for_fence);
+            let Some(syntax) = syntax else {
+                // Unknown languages are checked in full
+                return;
+            };
+            let regions = Regions {
+                comments: true,
+                strings: true,
+                identifiers: false,
+            };
+            let offset = block.start;
+            excluded.extend(
+                syntax
+                    .excluded(&content[block], regions)
+                    .into_iter()
+                    .map(|span| offset + span.start..offset + span.end),
+            );
+        }
+    }
+}
+
+/// Lines, without their line endings, and their starting offsets
+fn lines(content: &[u8]) -> impl Iterator<Item = (usize, &[u8])> {
+    content.lines_with_terminator().scan(0, |offset, line| {
+        let start = *offset;
+        *offset += line.len();
+        Some((start, line.trim_end_with(|c| c == '\n' || c == '\r')))
+    })
+}
+
+fn indent(line: &[u8]) -> usize {
+    line.iter()
+        .take_while(|b| **b == b' ' || **b == b'\t')
+        .count()
+}
+
+fn strip_indent(line: &[u8], max: usize) -> Option<&[u8]> {
+    let indent = line.iter().take_while(|b| **b == b' ').count();
+    (indent <= max).then(|| &line[indent..])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checked(excluded: Vec<Range<usize>>, content: &str) -> String {
+        let mut checked = content.to_owned().into_bytes();
+        for span in excluded {
+            for b in &mut checked[span] {
+                if !b.is_ascii_whitespace() {
+                    *b = b'.';
+                }
+            }
+        }
+        String::from_utf8(checked).unwrap()
+    }
+
+    #[test]
+    fn markdown_inline() {
+        let content = "Some `code` and [a link](https://example.com/teh) <a href=\"teh\">text</a>\n\n[ref]: https://example.com/teh\n";
+        let expected = "Some ...... and [a link](.......................) .. ...........text....\n\n[ref]: .......................\n";
+        assert_eq!(
+            checked(markdown(content.as_bytes(), CodeBlocks::Check), content),
+            expected
+        );
+    }
+
+    #[test]
+    fn markdown_unclosed_code_span() {
+        let content = "Some `code\n\nand more`\n";
+        assert_eq!(
+            checked(markdown(content.as_bytes(), CodeBlocks::Check), content),
+            content
+        );
+    }
+
+    #[test]
+    fn markdown_fence_skip() {
+        let content = "Prose\n```rust\nlet x = 1; // comment\n```\nProse\n";
+        let expected = "Prose\n.......\n... . . .. .. .......\n...\nProse\n";
+        assert_eq!(
+            checked(markdown(content.as_bytes(), CodeBlocks::Skip), content),
+            expected
+        );
+    }
+
+    #[test]
+    fn markdown_fence_language() {
+        let content = "````rust\nlet x = \"string\"; // comment\n```\n````\n~~~\nunknown\n~~~\n";
+        let expected = "........\n... . . \"string\". // comment\n...\n....\n...\nunknown\n...\n";
+        assert_eq!(
+            checked(markdown(content.as_bytes(), CodeBlocks::Language), content),
+            expected
+        );
+    }
+
+    #[test]
+    fn rst_inline() {
+        let content = "Some ``code`` and `a link <https://example.com/teh>`_\n\n.. _ref: https://example.com/teh\n";
+        let expected = "Some ........ and `a link ...........................\n\n.. _ref: .......................\n";
+        assert_eq!(
+            checked(rst(content.as_bytes(), CodeBlocks::Check), content),
+            expected
+        );
+    }
+
+    #[test]
+    fn rst_literal_blocks() {
+        let content = "Example::\n\n    let x = 1;\n\n.. code-block:: py\n\n    x = 1  # comment\n\nProse\n\n.. note::\n\n    More prose\n";
+        let expected = "Example::\n\n    let x = 1;\n\n.. code-block:: py\n\n    . . .  # comment\n\nProse\n\n.. note::\n\n    More prose\n";
+        assert_eq!(
+            checked(rst(content.as_bytes(), CodeBlocks::Language), content),
+            expected
+        );
+    }
+}
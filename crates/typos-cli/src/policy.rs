@@ -1,28 +1,37 @@
 pub struct ConfigStorage {
     arena: std::sync::Mutex<typed_arena::Arena<kstring::KString>>,
+    /// Strings already in `arena`, so reloading configs doesn't grow it
+    interned: std::sync::Mutex<std::collections::HashSet<&'static str>>,
 }
 
 impl ConfigStorage {
     pub fn new() -> Self {
         Self {
             arena: std::sync::Mutex::new(typed_arena::Arena::new()),
+            interned: Default::default(),
         }
     }
 
     fn get<'s>(&'s self, other: &str) -> &'s str {
+        let mut interned = self.interned.lock().unwrap();
+        if let Some(interned) = interned.get(other) {
+            return interned;
+        }
         // Safe because we the references are stable once created.
         //
         // Trying to get this handled inside of `typed_arena` directly, see
         // https://github.com/SimonSapin/rust-typed-arena/issues/49#issuecomment-809517312
-        unsafe {
-            std::mem::transmute::<&str, &str>(
+        let value = unsafe {
+            std::mem::transmute::<&str, &'static str>(
                 self.arena
                     .lock()
                     .unwrap()
                     .alloc(kstring::KString::from_ref(other))
                     .as_str(),
             )
-        }
+        };
+        interned.insert(value);
+        value
     }
 }
 
@@ -37,14 +46,24 @@ pub struct ConfigEngine<'s> {
 
     overrides: Option<crate::config::Config>,
     isolated: bool,
+    loader: crate::config::Loader,
+    changes: Option<crate::git::Changes>,
+    word_counts: Option<crate::dict::RepoWordCounts>,
 
     configs: std::collections::HashMap<std::path::PathBuf, DirConfig>,
     walk: Intern<crate::config::Walk>,
     tokenizer: Intern<typos::tokens::Tokenizer>,
     dict: Intern<crate::dict::Override<'s, 's, crate::dict::BuiltIn>>,
     ignore: Intern<Vec<regex::Regex>>,
+    dict_files: std::collections::HashMap<std::path::PathBuf, Vec<(&'s str, typos::Status<'s>)>>,
+    /// Settings for files matched by `[[overrides]]`, built on first use
+    overridden: std::sync::Mutex<std::collections::HashMap<OverriddenKey, FileConfig>>,
+    attributes: crate::attributes::GitAttributes,
 }
 
+/// The directory, file type, and matched `[[overrides]]` that settings were built for
+type OverriddenKey = (std::path::PathBuf, Option<kstring::KString>, Vec<usize>);
+
 impl<'s> ConfigEngine<'s> {
     pub fn new(storage: &'s ConfigStorage) -> Self {
         Self {
@@ -52,10 +71,16 @@ impl<'s> ConfigEngine<'s> {
             overrides: Default::default(),
             configs: Default::default(),
             isolated: false,
+            loader: Default::default(),
+            changes: None,
+            word_counts: None,
             walk: Default::default(),
             tokenizer: Default::default(),
             dict: Default::default(),
             ignore: Default::default(),
+            dict_files: Default::default(),
+            overridden: Default::default(),
+            attributes: Default::default(),
         }
     }
 
@@ -69,6 +94,67 @@ impl<'s> ConfigEngine<'s> {
         self
     }
 
+    /// Record where config values came from, see [`crate::config::Loader::track_provenance`]
+    pub fn set_track_provenance(&mut self, yes: bool) -> &mut Self {
+        self.loader = self.loader.track_provenance(yes);
+        self
+    }
+
+    /// Only check files and lines in `changes`
+    pub fn set_changes(&mut self, changes: Option<crate::git::Changes>) -> &mut Self {
+        self.changes = changes;
+        self
+    }
+
+    /// Rank corrections by how often they appear in `word_counts`, as well as the file
+    pub fn set_word_counts(
+        &mut self,
+        word_counts: Option<crate::dict::RepoWordCounts>,
+    ) -> &mut Self {
+        self.word_counts = word_counts;
+        self
+    }
+
+    /// Whether `rank-corrections` is set for any files under `cwd`
+    pub fn ranks_corrections(&self, cwd: &std::path::Path) -> bool {
+        debug_assert!(cwd.is_absolute(), "{} is not absolute", cwd.display());
+        self.configs
+            .iter()
+            .filter(|(root, _)| root.starts_with(cwd) || cwd.starts_with(root))
+            .any(|(_, dir)| {
+                dir.default.rank_corrections
+                    || dir.types.values().any(|file| file.rank_corrections)
+                    || dir
+                        .overrides
+                        .iter()
+                        .any(|o| o.engine.dict.rank_corrections == Some(true))
+            })
+    }
+
+    /// Forget loaded configs, so they are re-read by the next [`ConfigEngine::init_dir`]
+    pub fn reload(&mut self) {
+        self.configs.clear();
+        self.walk = Default::default();
+        self.tokenizer = Default::default();
+        self.dict = Default::default();
+        self.ignore = Default::default();
+        self.dict_files.clear();
+        self.overridden.get_mut().unwrap().clear();
+        self.attributes = Default::default();
+    }
+
+    /// Whether `path` should be checked when restricted by [`ConfigEngine::set_changes`]
+    pub fn is_changed(&self, path: &std::path::Path) -> bool {
+        self.changes
+            .as_ref()
+            .is_none_or(|changes| changes.contains_file(path))
+    }
+
+    /// Whether `path` is one of the `extend-dict-files`, which are expected to contain typos
+    pub fn is_dict_file(&self, path: &std::path::Path) -> bool {
+        self.dict_files.contains_key(path)
+    }
+
     pub fn walk(&self, cwd: &std::path::Path) -> &crate::config::Walk {
         debug_assert!(cwd.is_absolute(), "{} is not absolute", cwd.display());
         let dir = self
@@ -93,18 +179,70 @@ impl<'s> ConfigEngine<'s> {
     pub fn policy(&self, path: &std::path::Path) -> Policy<'_, '_, '_> {
         debug_assert!(path.is_absolute(), "{} is not absolute", path.display());
         let dir = self.get_dir(path).expect("`walk()` should be called first");
-        let (file_type, file_config) = dir.get_file_config(path);
+        let (file_type, mut file_config) = dir.get_file_config(path);
+        let overrides = dir.matched_overrides(path);
+        if !overrides.is_empty() {
+            file_config = self.get_overridden(dir, file_type, overrides);
+        }
         Policy {
             check_filenames: file_config.check_filenames,
             check_files: file_config.check_files,
             file_type,
             binary: file_config.binary,
+            regions: file_config.regions,
+            markup: file_config.markup,
+            code_blocks: file_config.code_blocks,
+            rank_corrections: file_config.rank_corrections,
+            word_counts: self.word_counts.as_ref(),
+            skip_generated: file_config.skip_generated,
+            max_file_size: file_config.max_file_size,
+            max_line_length: file_config.max_line_length,
+            generated_attribute: file_config
+                .skip_generated
+                .then(|| self.attributes.skip_reason(path))
+                .flatten(),
             tokenizer: self.get_tokenizer(&file_config),
             dict: self.get_dict(&file_config),
+            preferred_corrections: Some(
+                self.dict.get(file_config.dict).get_preferred_corrections(),
+            ),
             ignore: self.get_ignore(&file_config),
+            changes: self.changes.as_ref().and_then(|changes| changes.get(path)),
+            fingerprint: Some(file_config.fingerprint),
         }
     }
 
+    /// Settings for `file_type` with `overrides` applied
+    fn get_overridden(
+        &self,
+        dir: &DirConfig,
+        file_type: Option<&str>,
+        overrides: Vec<usize>,
+    ) -> FileConfig {
+        let key = (
+            dir.root.clone(),
+            file_type.map(kstring::KString::from_ref),
+            overrides,
+        );
+        // Held while building so each combination is only built once
+        let mut overridden = self.overridden.lock().unwrap();
+        if let Some(file_config) = overridden.get(&key) {
+            return *file_config;
+        }
+
+        let mut engine = file_type
+            .and_then(|file_type| dir.type_engines.get(file_type))
+            .unwrap_or(&dir.default_engine)
+            .clone();
+        for i in key.2.iter() {
+            engine.update(&dir.overrides[*i].engine);
+        }
+        engine.update(&dir.cli_overrides);
+        let file_config = self.build_file_config(engine);
+        overridden.insert(key, file_config);
+        file_config
+    }
+
     fn get_walk(&self, dir: &DirConfig) -> &crate::config::Walk {
         self.walk.get(dir.walk)
     }
@@ -135,40 +273,79 @@ impl<'s> ConfigEngine<'s> {
         cwd: &std::path::Path,
     ) -> Result<crate::config::Config, anyhow::Error> {
         debug_assert!(cwd.is_absolute(), "{} is not absolute", cwd.display());
-        let mut config = crate::config::Config::default();
+        let derived = if self.isolated {
+            Default::default()
+        } else {
+            self.derive_config(cwd)?
+        };
+        Ok(self.apply_overrides(derived))
+    }
 
-        if !self.isolated {
-            for ancestor in cwd.ancestors() {
-                if let Some(derived) = crate::config::Config::from_dir(ancestor)? {
-                    config.update(&derived);
-                    break;
-                }
+    /// The config from files for `cwd`
+    ///
+    /// The nearest config file wins, unless `cwd` is under a directory that is already loaded,
+    /// in which case it is merged on top of that directory's config.
+    fn derive_config(&self, cwd: &std::path::Path) -> Result<crate::config::Config, anyhow::Error> {
+        if let Some(config) = self.loader.load_dir(cwd)? {
+            return Ok(self.cascade(cwd, config));
+        }
+        if let Some(parent) = self.get_dir(cwd) {
+            return Ok(parent.derived.clone());
+        }
+        for ancestor in cwd.ancestors().skip(1) {
+            if let Some(config) = self.loader.load_dir(ancestor)? {
+                return Ok(config);
+            }
+        }
+        Ok(Default::default())
+    }
+
+    /// Merge `config`, from `dir`, on top of the nearest loaded ancestor's, unless it is a `root`
+    fn cascade(
+        &self,
+        dir: &std::path::Path,
+        config: crate::config::Config,
+    ) -> crate::config::Config {
+        if config.root() {
+            return config;
+        }
+        match dir.parent().and_then(|parent| self.get_dir(parent)) {
+            Some(parent) => {
+                let mut cascaded = parent.derived.clone();
+                cascaded.update(&config);
+                cascaded
             }
+            None => config,
         }
+    }
+
+    fn apply_overrides(&self, mut config: crate::config::Config) -> crate::config::Config {
         if let Some(overrides) = self.overrides.as_ref() {
             config.update(overrides);
         }
 
-        let mut types = Default::default();
-        std::mem::swap(&mut types, &mut config.type_.patterns);
-        let mut types = types
-            .into_iter()
-            .map(|(type_, type_engine)| {
-                let mut new_engine = config.default.clone();
-                new_engine.update(&type_engine.engine);
-                new_engine.update(&config.overrides);
-                let new_type_engine = crate::config::GlobEngineConfig {
-                    extend_glob: type_engine.extend_glob,
-                    engine: new_engine,
-                };
-                (type_, new_type_engine)
-            })
-            .collect();
-        std::mem::swap(&mut types, &mut config.type_.patterns);
-
-        config.default.update(&config.overrides);
-
-        Ok(config)
+        config.modify(|config| {
+            let mut types = Default::default();
+            std::mem::swap(&mut types, &mut config.type_.patterns);
+            let mut types = types
+                .into_iter()
+                .map(|(type_, type_engine)| {
+                    let mut new_engine = config.default.clone();
+                    new_engine.update(&type_engine.engine);
+                    new_engine.update(&config.overrides);
+                    let new_type_engine = crate::config::GlobEngineConfig {
+                        extend_glob: type_engine.extend_glob,
+                        engine: new_engine,
+                    };
+                    (type_, new_type_engine)
+                })
+                .collect();
+            std::mem::swap(&mut types, &mut config.type_.patterns);
+
+            config.default.update(&config.overrides);
+        });
+
+        config
     }
 
     pub fn init_dir(&mut self, cwd: &std::path::Path) -> Result<(), anyhow::Error> {
@@ -177,12 +354,28 @@ impl<'s> ConfigEngine<'s> {
             return Ok(());
         }
 
-        let config = self.load_config(cwd)?;
+        let derived = if self.isolated {
+            Default::default()
+        } else {
+            self.derive_config(cwd)?
+        };
+        self.init_dir_with(cwd, derived)
+    }
+
+    /// Load `derived`, the config from files for `cwd`
+    fn init_dir_with(
+        &mut self,
+        cwd: &std::path::Path,
+        derived: crate::config::Config,
+    ) -> Result<(), anyhow::Error> {
+        let config = self.apply_overrides(derived.clone());
         let crate::config::Config {
             files,
             mut default,
             type_,
+            glob_overrides,
             overrides,
+            ..
         } = config;
 
         let walk = self.walk.intern(files);
@@ -190,6 +383,7 @@ impl<'s> ConfigEngine<'s> {
         let mut type_matcher = crate::file_type::TypesBuilder::new();
         type_matcher.add_defaults();
         let mut types: std::collections::HashMap<_, _> = Default::default();
+        let mut type_engines: std::collections::HashMap<_, _> = Default::default();
         for (type_name, type_engine) in type_.patterns() {
             if type_engine.extend_glob.is_empty() {
                 if !type_matcher.contains_name(&type_name) {
@@ -205,29 +399,148 @@ impl<'s> ConfigEngine<'s> {
 
             let mut engine = default.clone();
             engine.update(&type_engine.engine);
+            type_engines.insert(type_name.clone(), engine.clone());
             engine.update(&overrides);
 
-            let type_config = self.init_file_config(engine);
+            let type_config = self.init_file_config(engine)?;
             types.insert(type_name, type_config);
         }
+        let default_engine = default.clone();
         default.update(&overrides);
-        let default = self.init_file_config(default);
+        let default = self.init_file_config(default)?;
+
+        let mut glob_matchers = Vec::new();
+        for glob_override in glob_overrides {
+            let base = glob_override.base.as_deref().unwrap_or(cwd);
+            let mut globs = ignore::gitignore::GitignoreBuilder::new(base);
+            for glob in glob_override.globs.iter() {
+                globs.add_line(None, glob)?;
+            }
+            self.prepare_file_config(&glob_override.engine)?;
+            glob_matchers.push(GlobOverride {
+                globs: globs.build()?,
+                engine: glob_override.engine,
+            });
+        }
 
         let dir = DirConfig {
+            root: cwd.to_owned(),
+            derived,
             walk,
             default,
             types,
             type_matcher: type_matcher.build()?,
+            overrides: glob_matchers,
+            default_engine,
+            type_engines,
+            cli_overrides: overrides,
         };
 
         self.configs.insert(cwd.to_owned(), dir);
         Ok(())
     }
 
-    fn init_file_config(&mut self, engine: crate::config::EngineConfig) -> FileConfig {
+    /// Load the configs in the directories of `walk`, so [`ConfigEngine::policy`] applies each to
+    /// its subtree, merged on top of its parent's
+    ///
+    /// `walk` should skip the same entries as checking does, like for `extend-exclude`.
+    /// Configs that can't be loaded are skipped, leaving their subtree to their parent's config.
+    /// [`ConfigEngine::init_dir`] must be called for its root first.
+    pub fn init_nested(
+        &mut self,
+        walk: impl IntoIterator<Item = Result<ignore::DirEntry, ignore::Error>>,
+    ) -> Result<(), anyhow::Error> {
+        if self.isolated {
+            return Ok(());
+        }
+
+        for entry in walk {
+            let Ok(entry) = entry else {
+                // Reported when checking
+                continue;
+            };
+            if entry.depth() == 0 || !entry.file_type().is_some_and(|t| t.is_dir()) {
+                continue;
+            }
+            let dir = entry.path().canonicalize()?;
+            if self.configs.contains_key(&dir) {
+                continue;
+            }
+            // Nested configs may belong to other projects, like test fixtures or vendored code
+            let config = match self.loader.load_dir(&dir) {
+                Ok(Some(config)) => self.cascade(&dir, config),
+                Ok(None) => continue,
+                Err(err) => {
+                    log::info!("{}: skipping config: {err}", dir.display());
+                    continue;
+                }
+            };
+            if let Err(err) = self.init_dir_with(&dir, config) {
+                log::info!("{}: skipping config: {err}", dir.display());
+            }
+        }
+        Ok(())
+    }
+
+    fn init_file_config(
+        &mut self,
+        engine: crate::config::EngineConfig,
+    ) -> Result<FileConfig, anyhow::Error> {
+        self.prepare_file_config(&engine)?;
+        Ok(self.build_file_config(engine))
+    }
+
+    /// Validate `engine` and load its `extend-dict-files`, for [`ConfigEngine::build_file_config`]
+    fn prepare_file_config(
+        &mut self,
+        engine: &crate::config::EngineConfig,
+    ) -> Result<(), anyhow::Error> {
+        let mut tokenizer_config = crate::config::TokenizerConfig::from_defaults();
+        tokenizer_config.update(&engine.tokenizer);
+        let mut dict_config = crate::config::DictConfig::from_defaults();
+        dict_config.update(&engine.dict);
+
+        if !tokenizer_config.ignore_hex() {
+            log::warn!("`ignore-hex` is deprecated");
+            if !tokenizer_config.identifier_leading_digits() {
+                log::warn!("`identifier-leading-digits` is deprecated");
+            }
+        }
+
+        for dictionary in dict_config.dictionaries() {
+            if !dictionary.is_available() && *dictionary != crate::config::Dictionary::Typos {
+                anyhow::bail!(
+                    "`{dictionary}` dictionary is unsupported, rebuild `typos-cli` with the `{}` feature",
+                    dictionary.feature()
+                );
+            }
+        }
+
+        for path in dict_config.extend_dict_files() {
+            let path = path.canonicalize().unwrap_or_else(|_| path.clone());
+            if !self.dict_files.contains_key(&path) {
+                let file = crate::dict::DictFile::from_file(&path)?;
+                let words = file.statuses(|s| self.storage.get(s));
+                self.dict_files.insert(path, words);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Build the settings for `engine`, once prepared by [`ConfigEngine::prepare_file_config`]
+    fn build_file_config(&self, engine: crate::config::EngineConfig) -> FileConfig {
+        let mut fingerprint = Fingerprint::new();
+        fingerprint.write_config(&engine);
         let binary = engine.binary();
         let check_filename = engine.check_filename();
         let check_file = engine.check_file();
+        let regions = crate::syntax::Regions::from_config(engine.check());
+        let markup = engine.markup();
+        let code_blocks = engine.code_blocks();
+        let skip_generated = engine.skip_generated();
+        let max_file_size = engine.max_file_size();
+        let max_line_length = engine.max_line_length();
         let crate::config::EngineConfig {
             tokenizer: tokenizer_user_config,
             dict: dict_user_config,
@@ -240,18 +553,12 @@ impl<'s> ConfigEngine<'s> {
         let mut dict_config = crate::config::DictConfig::from_defaults();
         dict_config.update(&dict_user_config);
 
-        if !tokenizer_config.ignore_hex() {
-            log::warn!("`ignore-hex` is deprecated");
-            if !tokenizer_config.identifier_leading_digits() {
-                log::warn!("`identifier-leading-digits` is deprecated");
-            }
-        }
-
         let tokenizer = typos::tokens::TokenizerBuilder::new()
             .unicode(tokenizer_config.unicode())
             .build();
 
-        let dict = crate::dict::BuiltIn::new(dict_config.locale());
+        let dict = crate::dict::BuiltIn::new(dict_config.locale())
+            .with_dictionaries(dict_config.dictionaries().to_vec());
         let mut dict = crate::dict::Override::new(dict);
         dict.ignored_identifiers(dict_config.extend_ignore_identifiers_re());
         dict.identifiers(
@@ -265,6 +572,17 @@ impl<'s> ConfigEngine<'s> {
                 .extend_words()
                 .map(|(k, v)| (self.storage.get(k), self.storage.get(v))),
         );
+        for path in dict_config.extend_dict_files() {
+            let path = path.canonicalize().unwrap_or_else(|_| path.clone());
+            let words = &self.dict_files[&path];
+            fingerprint.write_dict_file(words);
+            dict.dict_file_words(words.iter().cloned());
+        }
+        dict.preferred_corrections(
+            dict_config
+                .preferred_corrections()
+                .map(|(k, v)| (self.storage.get(k), self.storage.get(v))),
+        );
 
         let dict = self.dict.intern(dict);
         let tokenizer = self.tokenizer.intern(tokenizer);
@@ -275,28 +593,99 @@ impl<'s> ConfigEngine<'s> {
             check_filenames: check_filename,
             check_files: check_file,
             binary,
+            regions,
+            markup,
+            code_blocks,
+            rank_corrections: dict_config.rank_corrections(),
+            skip_generated,
+            max_file_size,
+            max_line_length,
             tokenizer,
             dict,
             ignore,
+            fingerprint: fingerprint.finish(),
+        }
+    }
+}
+
+/// Hash the settings behind a [`FileConfig`], so cached results can be invalidated
+struct Fingerprint(siphasher::sip128::SipHasher13);
+
+impl Fingerprint {
+    fn new() -> Self {
+        use std::hash::Hasher as _;
+
+        let mut hasher = siphasher::sip128::SipHasher13::new();
+        // Built-in dictionaries are versioned with the crate
+        hasher.write(env!("CARGO_PKG_VERSION").as_bytes());
+        Self(hasher)
+    }
+
+    fn write_config(&mut self, engine: &crate::config::EngineConfig) {
+        let value = serde_json::to_value(engine).expect("config is always serializable");
+        self.write_value(&value);
+    }
+
+    fn write_dict_file(&mut self, words: &[(&str, typos::Status<'_>)]) {
+        for (word, status) in words {
+            self.write_str(word);
+            let status = serde_json::to_value(status).expect("status is always serializable");
+            self.write_value(&status);
+        }
+    }
+
+    /// Hash maps in sorted order, as `HashMap` iteration order isn't stable between runs
+    fn write_value(&mut self, value: &serde_json::Value) {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut entries: Vec<_> = map.iter().collect();
+                entries.sort_unstable_by_key(|(key, _)| *key);
+                self.write_str("{");
+                for (key, value) in entries {
+                    self.write_str(key);
+                    self.write_value(value);
+                }
+                self.write_str("}");
+            }
+            serde_json::Value::Array(values) => {
+                self.write_str("[");
+                for value in values {
+                    self.write_value(value);
+                }
+                self.write_str("]");
+            }
+            _ => self.write_str(&value.to_string()),
         }
     }
+
+    fn write_str(&mut self, s: &str) {
+        use std::hash::Hasher as _;
+
+        self.0.write_usize(s.len());
+        self.0.write(s.as_bytes());
+    }
+
+    fn finish(&self) -> u128 {
+        use siphasher::sip128::Hasher128 as _;
+
+        self.0.finish128().as_u128()
+    }
 }
 
+/// Values referenced by index, which stay put as more are added
 struct Intern<T> {
-    data: Vec<T>,
+    data: boxcar::Vec<T>,
 }
 
 impl<T> Intern<T> {
     pub(crate) fn new() -> Self {
         Self {
-            data: Default::default(),
+            data: boxcar::Vec::new(),
         }
     }
 
-    pub(crate) fn intern(&mut self, value: T) -> usize {
-        let symbol = self.data.len();
-        self.data.push(value);
-        symbol
+    pub(crate) fn intern(&self, value: T) -> usize {
+        self.data.push(value)
     }
 
     pub(crate) fn get(&self, symbol: usize) -> &T {
@@ -312,13 +701,32 @@ impl<T> Default for Intern<T> {
 
 #[derive(Clone, Debug)]
 struct DirConfig {
+    root: std::path::PathBuf,
+    /// The config from files, for nested configs to be merged on top of
+    derived: crate::config::Config,
     walk: usize,
     default: FileConfig,
     types: std::collections::HashMap<kstring::KString, FileConfig>,
     type_matcher: crate::file_type::Types,
+    /// `[[overrides]]`, in the order they apply
+    overrides: Vec<GlobOverride>,
+    /// What `default` and `types` were built from, for applying `overrides` to
+    default_engine: crate::config::EngineConfig,
+    type_engines: std::collections::HashMap<kstring::KString, crate::config::EngineConfig>,
+    /// Command-line settings, which take precedence over `overrides`
+    cli_overrides: crate::config::EngineConfig,
 }
 
 impl DirConfig {
+    fn matched_overrides(&self, path: &std::path::Path) -> Vec<usize> {
+        self.overrides
+            .iter()
+            .enumerate()
+            .filter(|(_, glob_override)| glob_override.is_match(path))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
     fn get_file_config(&self, path: &std::path::Path) -> (Option<&str>, FileConfig) {
         let name = self.type_matcher.file_matched(path);
 
@@ -339,6 +747,22 @@ impl DirConfig {
     }
 }
 
+#[derive(Clone, Debug)]
+struct GlobOverride {
+    globs: ignore::gitignore::Gitignore,
+    engine: crate::config::EngineConfig,
+}
+
+impl GlobOverride {
+    fn is_match(&self, path: &std::path::Path) -> bool {
+        path.starts_with(self.globs.path())
+            && self
+                .globs
+                .matched_path_or_any_parents(path, false)
+                .is_ignore()
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 struct FileConfig {
     tokenizer: usize,
@@ -346,7 +770,15 @@ struct FileConfig {
     check_filenames: bool,
     check_files: bool,
     binary: bool,
+    regions: crate::syntax::Regions,
+    markup: bool,
+    code_blocks: crate::config::CodeBlocks,
+    rank_corrections: bool,
+    skip_generated: bool,
+    max_file_size: Option<u64>,
+    max_line_length: Option<usize>,
     ignore: usize,
+    fingerprint: u128,
 }
 
 #[non_exhaustive]
@@ -356,9 +788,32 @@ pub struct Policy<'t, 'd, 'i> {
     pub check_files: bool,
     pub file_type: Option<&'d str>,
     pub binary: bool,
+    /// Only check these regions, for file types with a known syntax
+    pub regions: crate::syntax::Regions,
+    /// Skip markup syntax for markup file types
+    pub markup: bool,
+    pub code_blocks: crate::config::CodeBlocks,
+    /// Order corrections by how likely they are meant, see [`crate::dict::rank_corrections`]
+    pub rank_corrections: bool,
+    /// How often words appear across the files being checked, for `rank_corrections`
+    pub word_counts: Option<&'d crate::dict::RepoWordCounts>,
+    /// Skip files that look generated or vendored
+    pub skip_generated: bool,
+    /// Why `.gitattributes` marks the file to be skipped, when `skip_generated`
+    pub generated_attribute: Option<&'static str>,
+    /// Skip files larger than this many bytes
+    pub max_file_size: Option<u64>,
+    /// Skip lines longer than this many bytes
+    pub max_line_length: Option<usize>,
     pub tokenizer: &'t typos::tokens::Tokenizer,
     pub dict: &'d dyn typos::Dictionary,
+    /// To mark typos resolved by `preferred-corrections` in `dict`
+    pub preferred_corrections: Option<&'d crate::dict::PreferredCorrections<'d>>,
     pub ignore: &'i [regex::Regex],
+    /// Only check these lines, when set
+    pub changes: Option<&'d crate::git::FileChanges>,
+    /// Identifies the settings this policy was derived from, for caching results
+    pub fingerprint: Option<u128>,
 }
 
 impl Policy<'_, '_, '_> {
@@ -378,9 +833,21 @@ impl Default for Policy<'_, '_, '_> {
             check_files: true,
             file_type: None,
             binary: false,
+            regions: crate::syntax::Regions::ALL,
+            markup: false,
+            code_blocks: crate::config::CodeBlocks::Check,
+            rank_corrections: false,
+            word_counts: None,
+            skip_generated: false,
+            generated_attribute: None,
+            max_file_size: None,
+            max_line_length: None,
             tokenizer: &DEFAULT_TOKENIZER,
             dict: &DEFAULT_DICT,
+            preferred_corrections: None,
             ignore: DEFAULT_IGNORE,
+            changes: None,
+            fingerprint: None,
         }
     }
 }
@@ -472,6 +939,50 @@ mod test {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_nested_configs() {
+        let temp = assert_fs::TempDir::new().unwrap();
+        let root = temp.path().canonicalize().unwrap();
+        let sub = root.join("sub");
+        let broken = root.join("broken");
+        std::fs::create_dir_all(&sub).unwrap();
+        std::fs::create_dir_all(&broken).unwrap();
+        std::fs::write(
+            root.join("_typos.toml"),
+            "[default.extend-words]\nabck = \"abck\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            sub.join("_typos.toml"),
+            "[default.extend-words]\nteh = \"teh\"\n",
+        )
+        .unwrap();
+        std::fs::write(broken.join("_typos.toml"), "unknown = true\n").unwrap();
+        let is_valid = |engine: &ConfigEngine<'_>, path: &std::path::Path, word: &str| {
+            let word = typos::tokens::Word::new_unchecked(word, typos::tokens::Case::Lower, 0);
+            engine.policy(path).dict.correct_word(word) == Some(typos::Status::Valid)
+        };
+
+        // Run on its own, the nearest config is used
+        let storage = ConfigStorage::new();
+        let mut engine = ConfigEngine::new(&storage);
+        engine.init_dir(&sub).unwrap();
+        assert!(is_valid(&engine, &sub.join("file.txt"), "teh"));
+        assert!(!is_valid(&engine, &sub.join("file.txt"), "abck"));
+
+        // Nested, it is merged on top of its parent's
+        let storage = ConfigStorage::new();
+        let mut engine = ConfigEngine::new(&storage);
+        engine.init_dir(&root).unwrap();
+        engine
+            .init_nested(ignore::WalkBuilder::new(&root).build())
+            .unwrap();
+        assert!(is_valid(&engine, &sub.join("file.txt"), "teh"));
+        assert!(is_valid(&engine, &sub.join("file.txt"), "abck"));
+        assert!(!is_valid(&engine, &root.join("file.txt"), "teh"));
+        assert!(is_valid(&engine, &broken.join("file.txt"), "abck"));
+    }
+
     #[test]
     fn test_policy_default() {
         let storage = ConfigStorage::new();
@@ -555,4 +1066,38 @@ mod test {
         let policy = engine.policy(&cwd.join(NEVER_EXIST_TYPE));
         assert!(!policy.binary);
     }
+
+    #[test]
+    fn test_policy_glob_overrides() {
+        let storage = ConfigStorage::new();
+        let mut engine = ConfigEngine::new(&storage);
+        engine.set_isolated(true);
+
+        let config = crate::config::Config::from_toml(
+            r#"[[overrides]]
+globs = ["fixtures/"]
+binary = true
+check-file = false
+
+[[overrides]]
+globs = ["*.bin"]
+binary = false
+"#,
+        )
+        .unwrap();
+        engine.set_overrides(config);
+
+        let cwd = std::path::Path::new(".").canonicalize().unwrap();
+        engine.init_dir(&cwd).unwrap();
+        let policy = engine.policy(&cwd.join("Cargo.toml"));
+        assert!(!policy.binary);
+        assert!(policy.check_files);
+        let policy = engine.policy(&cwd.join("fixtures/nested/Cargo.toml"));
+        assert!(policy.binary);
+        assert!(!policy.check_files);
+        assert_eq!(policy.file_type, Some("rust"));
+        let policy = engine.policy(&cwd.join("fixtures/data.bin"));
+        assert!(!policy.binary);
+        assert!(!policy.check_files);
+    }
 }
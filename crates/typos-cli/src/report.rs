@@ -1,6 +1,7 @@
 #![allow(clippy::needless_update)]
 
 use std::borrow::Cow;
+use std::ops::Range;
 
 pub trait Report: Send + Sync {
     fn report(&self, msg: Message<'_>) -> Result<(), std::io::Error>;
@@ -16,7 +17,9 @@ pub trait Report: Send + Sync {
 #[non_exhaustive]
 pub enum Message<'m> {
     BinaryFile(BinaryFile<'m>),
+    Skipped(Skipped<'m>),
     Typo(Typo<'m>),
+    UnusedDirective(UnusedDirective<'m>),
     FileType(FileType<'m>),
     File(File<'m>),
     Parse(Parse<'m>),
@@ -27,7 +30,9 @@ impl<'m> Message<'m> {
     pub fn is_typo(&self) -> bool {
         match self {
             Message::BinaryFile(_) => false,
+            Message::Skipped(_) => false,
             Message::Typo(c) => !c.corrections.is_valid(),
+            Message::UnusedDirective(_) => false,
             Message::FileType(_) => false,
             Message::File(_) => false,
             Message::Parse(_) => false,
@@ -38,7 +43,9 @@ impl<'m> Message<'m> {
     pub fn is_error(&self) -> bool {
         match self {
             Message::BinaryFile(_) => false,
+            Message::Skipped(_) => false,
             Message::Typo(_) => false,
+            Message::UnusedDirective(_) => false,
             Message::FileType(_) => false,
             Message::File(_) => false,
             Message::Parse(_) => false,
@@ -60,6 +67,14 @@ impl<'m> Message<'m> {
                 let error = error.context(context);
                 Message::Error(error)
             }
+            Message::Skipped(skipped) => {
+                let skipped = skipped.context(context);
+                Message::Skipped(skipped)
+            }
+            Message::UnusedDirective(unused) => {
+                let unused = unused.context(context);
+                Message::UnusedDirective(unused)
+            }
             _ => self,
         }
     }
@@ -72,6 +87,44 @@ pub struct BinaryFile<'m> {
     pub path: &'m std::path::Path,
 }
 
+impl BinaryFile<'_> {
+    /// Identify this skip by its path, like [`Typo::fingerprint`]
+    pub fn fingerprint(&self) -> String {
+        let mut hash = Fnv1a::new();
+        hash.write(context_path(Some(&PathContext { path: self.path }.into())).as_bytes());
+        format!("{:016x}", hash.finish())
+    }
+}
+
+/// A file or line that wasn't checked, like for `skip-generated` or `max-file-size`
+#[derive(Clone, Debug, serde::Serialize, derive_setters::Setters)]
+#[non_exhaustive]
+pub struct Skipped<'m> {
+    #[serde(flatten)]
+    pub context: Option<Context<'m>>,
+    pub reason: Cow<'m, str>,
+}
+
+impl Skipped<'_> {
+    /// Identify this skip by its path and reason, like [`Typo::fingerprint`]
+    pub fn fingerprint(&self) -> String {
+        let mut hash = Fnv1a::new();
+        hash.write(context_path(self.context.as_ref()).as_bytes());
+        hash.write(&[0]);
+        hash.write(self.reason.as_bytes());
+        format!("{:016x}", hash.finish())
+    }
+}
+
+impl std::fmt::Display for Skipped<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match &self.context {
+            Some(context) => write!(f, "Skipping {context}: {}", self.reason),
+            None => write!(f, "Skipping: {}", self.reason),
+        }
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, derive_setters::Setters)]
 #[non_exhaustive]
 pub struct Typo<'m> {
@@ -82,6 +135,56 @@ pub struct Typo<'m> {
     pub byte_offset: usize,
     pub typo: &'m str,
     pub corrections: typos::Status<'m>,
+    /// Whether `corrections` was narrowed down by `preferred-corrections`
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub preferred: bool,
+}
+
+impl Typo<'_> {
+    /// Identify this typo by its path, text, and surrounding line
+    ///
+    /// Unlike the line number, this is stable when unrelated lines are added or removed.
+    pub fn fingerprint(&self) -> String {
+        let line = String::from_utf8_lossy(&self.buffer);
+
+        let mut hash = Fnv1a::new();
+        hash.write(context_path(self.context.as_ref()).as_bytes());
+        hash.write(&[0]);
+        hash.write(self.typo.as_bytes());
+        hash.write(&[0]);
+        for (i, word) in line.split_whitespace().enumerate() {
+            if i != 0 {
+                hash.write(b" ");
+            }
+            hash.write(word.as_bytes());
+        }
+        format!("{:016x}", hash.finish())
+    }
+
+    /// The line with the typo, see [`lossy_span`]
+    pub fn line(&self) -> (Cow<'_, str>, Range<usize>) {
+        lossy_span(&self.buffer, self.byte_offset, self.typo.len())
+    }
+
+    /// 1-based columns of the typo within its line, end-exclusive
+    pub fn columns(&self, unit: ColumnUnit) -> Range<usize> {
+        if unit == ColumnUnit::Byte {
+            // Count what is in the file, rather than any replacement characters
+            return self.byte_offset + 1..self.byte_offset + self.typo.len() + 1;
+        }
+        let (line, span) = self.line();
+        let start = unit.count(&line[..span.start]) + 1;
+        let end = start + unit.count(&line[span]);
+        start..end
+    }
+
+    /// Byte offset of the typo within the file, `None` for typos in the file name
+    pub fn offset(&self) -> Option<usize> {
+        match &self.context {
+            Some(Context::File(context)) => Some(context.line_start + self.byte_offset),
+            _ => None,
+        }
+    }
 }
 
 impl Default for Typo<'_> {
@@ -92,7 +195,38 @@ impl Default for Typo<'_> {
             byte_offset: 0,
             typo: "",
             corrections: typos::Status::Invalid,
+            preferred: false,
+        }
+    }
+}
+
+/// A `typos:` directive that didn't suppress any typos
+#[derive(Clone, Debug, serde::Serialize, derive_setters::Setters)]
+#[non_exhaustive]
+pub struct UnusedDirective<'m> {
+    #[serde(flatten)]
+    pub context: Option<Context<'m>>,
+    /// The directive, like `ignore-next-line`
+    pub directive: Cow<'m, str>,
+}
+
+impl UnusedDirective<'_> {
+    /// Identify this directive by its path and kind, like [`Typo::fingerprint`]
+    pub fn fingerprint(&self) -> String {
+        let mut hash = Fnv1a::new();
+        hash.write(context_path(self.context.as_ref()).as_bytes());
+        hash.write(&[0]);
+        hash.write(self.directive.as_bytes());
+        format!("{:016x}", hash.finish())
+    }
+}
+
+impl std::fmt::Display for UnusedDirective<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        if let Some(context) = &self.context {
+            write!(f, "{context}: ")?;
         }
+        write!(f, "`typos: {}` does not suppress any typos", self.directive)
     }
 }
 
@@ -118,6 +252,9 @@ impl std::fmt::Display for Context<'_> {
 pub struct FileContext<'m> {
     pub path: &'m std::path::Path,
     pub line_num: usize,
+    /// Byte offset of the start of the line within the file
+    #[serde(skip)]
+    pub line_start: usize,
 }
 
 impl Default for FileContext<'_> {
@@ -125,6 +262,7 @@ impl Default for FileContext<'_> {
         Self {
             path: std::path::Path::new("-"),
             line_num: 0,
+            line_start: 0,
         }
     }
 }
@@ -143,6 +281,83 @@ impl Default for PathContext<'_> {
     }
 }
 
+/// How to count columns, as editors and report formats disagree
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum ColumnUnit {
+    Byte,
+    /// Unicode scalar values
+    #[default]
+    Char,
+    /// UTF-16 code units, like LSP and JavaScript
+    Utf16,
+}
+
+impl ColumnUnit {
+    /// The length of `text` in this unit
+    pub fn count(self, text: &str) -> usize {
+        match self {
+            ColumnUnit::Byte => text.len(),
+            ColumnUnit::Char => text.chars().count(),
+            ColumnUnit::Utf16 => text.chars().map(char::len_utf16).sum(),
+        }
+    }
+
+    pub const fn variants() -> [&'static str; 3] {
+        ["byte", "char", "utf16"]
+    }
+}
+
+impl std::str::FromStr for ColumnUnit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "byte" => Ok(ColumnUnit::Byte),
+            "char" => Ok(ColumnUnit::Char),
+            "utf16" => Ok(ColumnUnit::Utf16),
+            _ => Err("valid values: byte, char, utf16".to_owned()),
+        }
+    }
+}
+
+impl std::fmt::Display for ColumnUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            ColumnUnit::Byte => write!(f, "byte"),
+            ColumnUnit::Char => write!(f, "char"),
+            ColumnUnit::Utf16 => write!(f, "utf16"),
+        }
+    }
+}
+
+/// Convert `line` to a `str` for display, adjusting the span `start..start + len` to match
+///
+/// Invalid UTF-8 is replaced, which can change the length of the text before the span.
+pub fn lossy_span(line: &[u8], start: usize, len: usize) -> (Cow<'_, str>, Range<usize>) {
+    let end = start + len;
+
+    if let Ok(line) = std::str::from_utf8(line) {
+        return (Cow::Borrowed(line), start..end);
+    }
+
+    let prefix = &line[0..start];
+    let prefix = String::from_utf8_lossy(prefix);
+
+    let middle = &line[start..end];
+    let middle = String::from_utf8_lossy(middle);
+
+    let suffix = &line[end..];
+    let suffix = String::from_utf8_lossy(suffix);
+
+    let span_start = prefix.len();
+    let span_end = span_start + middle.len();
+
+    (
+        Cow::Owned(format!("{prefix}{middle}{suffix}")),
+        span_start..span_end,
+    )
+}
+
 #[derive(Copy, Clone, Debug, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 #[non_exhaustive]
@@ -224,6 +439,27 @@ impl Error<'_> {
     pub fn new(msg: String) -> Self {
         Self { context: None, msg }
     }
+
+    /// Identify this error by its path and message, like [`Typo::fingerprint`]
+    pub fn fingerprint(&self) -> String {
+        let mut hash = Fnv1a::new();
+        hash.write(context_path(self.context.as_ref()).as_bytes());
+        hash.write(&[0]);
+        hash.write(self.msg.as_bytes());
+        format!("{:016x}", hash.finish())
+    }
+}
+
+/// Normalize the path so fingerprints match across platforms
+fn context_path(context: Option<&Context<'_>>) -> String {
+    let path = match context {
+        Some(Context::File(context)) => context.path,
+        Some(Context::Path(context)) => context.path,
+        None => std::path::Path::new(""),
+    };
+    path.display()
+        .to_string()
+        .replace(std::path::MAIN_SEPARATOR, "/")
 }
 
 impl Default for Error<'_> {
@@ -234,3 +470,51 @@ impl Default for Error<'_> {
         }
     }
 }
+
+/// Hash that is stable across platforms and releases, unlike `std`s
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf29ce484222325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn columns() {
+        let typo = Typo::default()
+            .buffer(Cow::Borrowed("😀 teh".as_bytes()))
+            .byte_offset(5)
+            .typo("teh");
+        assert_eq!(typo.columns(ColumnUnit::Byte), 6..9);
+        assert_eq!(typo.columns(ColumnUnit::Char), 3..6);
+        assert_eq!(typo.columns(ColumnUnit::Utf16), 4..7);
+    }
+
+    #[test]
+    fn columns_with_invalid_utf8() {
+        let typo = Typo::default()
+            .buffer(Cow::Borrowed(b"\xff\xfe teh"))
+            .byte_offset(3)
+            .typo("teh");
+        let (line, span) = typo.line();
+        assert_eq!(&line[span], "teh");
+        assert_eq!(typo.columns(ColumnUnit::Char), 4..7);
+        assert_eq!(typo.columns(ColumnUnit::Byte), 4..7);
+    }
+}
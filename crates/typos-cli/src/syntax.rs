@@ -0,0 +1,482 @@
+//! Lightweight lexing of source files into comments, string literals, and code
+//!
+//! This is not a full parser, only enough to tell prose apart from identifiers.
+
+use std::ops::Range;
+
+/// Parts of a file to spell check
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Regions {
+    pub comments: bool,
+    pub strings: bool,
+    /// Everything outside of comments and strings
+    pub identifiers: bool,
+}
+
+impl Regions {
+    pub const ALL: Self = Self {
+        comments: true,
+        strings: true,
+        identifiers: true,
+    };
+
+    pub fn from_config(regions: &[crate::config::Region]) -> Self {
+        let mut selected = Self {
+            comments: false,
+            strings: false,
+            identifiers: false,
+        };
+        for region in regions {
+            match region {
+                crate::config::Region::Comments => selected.comments = true,
+                crate::config::Region::Strings => selected.strings = true,
+                crate::config::Region::Identifiers => selected.identifiers = true,
+            }
+        }
+        selected
+    }
+
+    pub fn is_all(&self) -> bool {
+        *self == Self::ALL
+    }
+}
+
+impl Default for Regions {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Kind {
+    Comment,
+    String,
+}
+
+/// Comment and string-literal delimiters for a language
+#[derive(Copy, Clone, Debug)]
+pub struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comments: &'static [(&'static str, &'static str)],
+    /// Checked in order, so longer delimiters must come first
+    strings: &'static [Quote],
+    /// Skip `'x'`, so `'"'` doesn't open a string, while telling it apart from a lifetime
+    char_literals: bool,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Quote {
+    open: &'static str,
+    close: &'static str,
+    escape: bool,
+    multiline: bool,
+}
+
+impl Quote {
+    const fn new(open: &'static str, close: &'static str) -> Self {
+        Self {
+            open,
+            close,
+            escape: true,
+            multiline: false,
+        }
+    }
+
+    const fn raw(mut self) -> Self {
+        self.escape = false;
+        self
+    }
+
+    const fn multiline(mut self) -> Self {
+        self.multiline = true;
+        self
+    }
+}
+
+const DOUBLE: Quote = Quote::new("\"", "\"");
+const SINGLE: Quote = Quote::new("'", "'");
+
+const C: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[DOUBLE, SINGLE],
+    char_literals: false,
+};
+const CSS: Syntax = Syntax {
+    line_comments: &[],
+    block_comments: &[("/*", "*/")],
+    strings: &[DOUBLE, SINGLE],
+    char_literals: false,
+};
+const GO: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[DOUBLE, SINGLE, Quote::new("`", "`").raw().multiline()],
+    char_literals: false,
+};
+const JS: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[DOUBLE, SINGLE, Quote::new("`", "`").multiline()],
+    char_literals: false,
+};
+const KOTLIN: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[
+        Quote::new("\"\"\"", "\"\"\"").raw().multiline(),
+        DOUBLE,
+        SINGLE,
+    ],
+    char_literals: false,
+};
+const RUST: Syntax = Syntax {
+    line_comments: &["//"],
+    block_comments: &[("/*", "*/")],
+    strings: &[
+        Quote::new("r###\"", "\"###").raw().multiline(),
+        Quote::new("r##\"", "\"##").raw().multiline(),
+        Quote::new("r#\"", "\"#").raw().multiline(),
+        Quote::new("r\"", "\"").raw().multiline(),
+        DOUBLE.multiline(),
+    ],
+    char_literals: true,
+};
+const PYTHON: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[],
+    strings: &[
+        Quote::new("\"\"\"", "\"\"\"").multiline(),
+        Quote::new("'''", "'''").multiline(),
+        DOUBLE,
+        SINGLE,
+    ],
+    char_literals: false,
+};
+const SHELL: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[],
+    strings: &[DOUBLE.multiline(), SINGLE.raw().multiline()],
+    char_literals: false,
+};
+const RUBY: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[("=begin", "=end")],
+    strings: &[DOUBLE, SINGLE],
+    char_literals: false,
+};
+const TOML: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[],
+    strings: &[
+        Quote::new("\"\"\"", "\"\"\"").multiline(),
+        Quote::new("'''", "'''").raw().multiline(),
+        DOUBLE,
+        SINGLE.raw(),
+    ],
+    char_literals: false,
+};
+const YAML: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[],
+    strings: &[DOUBLE, SINGLE.raw()],
+    char_literals: false,
+};
+const HASH: Syntax = Syntax {
+    line_comments: &["#"],
+    block_comments: &[],
+    strings: &[DOUBLE, SINGLE],
+    char_literals: false,
+};
+const LUA: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("--[[", "]]")],
+    strings: &[DOUBLE, SINGLE, Quote::new("[[", "]]").raw().multiline()],
+    char_literals: false,
+};
+const SQL: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("/*", "*/")],
+    strings: &[SINGLE.raw(), DOUBLE.raw()],
+    char_literals: false,
+};
+const HASKELL: Syntax = Syntax {
+    line_comments: &["--"],
+    block_comments: &[("{-", "-}")],
+    strings: &[DOUBLE],
+    char_literals: false,
+};
+const LISP: Syntax = Syntax {
+    line_comments: &[";"],
+    block_comments: &[],
+    strings: &[DOUBLE.multiline()],
+    char_literals: false,
+};
+
+impl Syntax {
+    /// Look up the syntax for a file type from `--type-list`
+    pub fn for_type(file_type: &str) -> Option<&'static Self> {
+        let syntax = match file_type {
+            "c" | "cpp" | "h" | "cuda" | "objc" | "objcpp" | "java" | "csharp" | "scala"
+            | "swift" | "dart" | "groovy" | "gradle" | "protobuf" | "solidity" | "php" | "zig"
+            | "v" | "vala" | "d" | "hare" | "wgsl" | "thrift" => &C,
+            "css" | "less" => &CSS,
+            "go" => &GO,
+            "js" | "ts" => &JS,
+            "kotlin" => &KOTLIN,
+            "rust" => &RUST,
+            "py" | "cython" => &PYTHON,
+            "sh" | "zsh" | "fish" => &SHELL,
+            "ruby" => &RUBY,
+            "toml" => &TOML,
+            "yaml" => &YAML,
+            "perl" | "r" | "make" | "cmake" | "docker" | "nim" | "elixir" | "tcl" => &HASH,
+            "lua" => &LUA,
+            "sql" => &SQL,
+            "hs" | "elm" => &HASKELL,
+            "lisp" | "elisp" | "clojure" | "racket" => &LISP,
+            _ => return None,
+        };
+        Some(syntax)
+    }
+
+    /// Look up the syntax for a code block's language, like ```` ```python ````
+    pub fn for_fence(lang: &str) -> Option<&'static Self> {
+        let lang = lang.to_ascii_lowercase();
+        let file_type = match lang.as_str() {
+            "rs" => "rust",
+            "python" | "python3" => "py",
+            "javascript" | "jsx" | "mjs" => "js",
+            "typescript" | "tsx" => "ts",
+            "bash" | "shell" => "sh",
+            "c++" | "cxx" | "cc" | "hpp" => "cpp",
+            "c#" | "cs" => "csharp",
+            "golang" => "go",
+            "rb" => "ruby",
+            "kt" => "kotlin",
+            "yml" => "yaml",
+            "haskell" => "hs",
+            lang => lang,
+        };
+        Self::for_type(file_type)
+    }
+
+    /// Byte ranges of `content` outside of the `regions` to check
+    pub fn excluded(&self, content: &[u8], regions: Regions) -> Vec<Range<usize>> {
+        let mut excluded = Vec::new();
+        let mut code_start = 0;
+        for (kind, span) in self.lex(content) {
+            if !regions.identifiers && code_start < span.start {
+                excluded.push(code_start..span.start);
+            }
+            let check = match kind {
+                Kind::Comment => regions.comments,
+                Kind::String => regions.strings,
+            };
+            code_start = span.end;
+            if !check {
+                excluded.push(span);
+            }
+        }
+        if !regions.identifiers && code_start < content.len() {
+            excluded.push(code_start..content.len());
+        }
+        excluded
+    }
+
+    fn lex(&self, content: &[u8]) -> Vec<(Kind, Range<usize>)> {
+        let mut spans = Vec::new();
+        let mut i = 0;
+        'outer: while i < content.len() {
+            let rest = &content[i..];
+            if self
+                .line_comments
+                .iter()
+                .any(|open| rest.starts_with(open.as_bytes()))
+            {
+                let end = find(content, i, b"\n").unwrap_or(content.len());
+                spans.push((Kind::Comment, i..end));
+                i = end;
+                continue;
+            }
+            for (open, close) in self.block_comments {
+                if rest.starts_with(open.as_bytes()) {
+                    let end = find(content, i + open.len(), close.as_bytes())
+                        .map(|end| end + close.len())
+                        .unwrap_or(content.len());
+                    spans.push((Kind::Comment, i..end));
+                    i = end;
+                    continue 'outer;
+                }
+            }
+            if self.char_literals {
+                if let Some(len) = char_literal_len(rest) {
+                    i += len;
+                    continue;
+                }
+            }
+            for quote in self.strings {
+                if rest.starts_with(quote.open.as_bytes()) && !is_prefixed(content, i, quote) {
+                    let end = quote.end(content, i + quote.open.len());
+                    spans.push((Kind::String, i..end));
+                    i = end;
+                    continue 'outer;
+                }
+            }
+            i += 1;
+        }
+        spans
+    }
+}
+
+impl Quote {
+    fn end(&self, content: &[u8], mut i: usize) -> usize {
+        while i < content.len() {
+            if self.escape && content[i] == b'\\' {
+                i += 2;
+            } else if content[i..].starts_with(self.close.as_bytes()) {
+                return i + self.close.len();
+            } else if !self.multiline && content[i] == b'\n' {
+                // Unterminated
+                return i;
+            } else {
+                i += 1;
+            }
+        }
+        content.len()
+    }
+}
+
+/// The length of the char literal at the start of `rest`, like `'a'` or `'\''`, but not `'a`
+fn char_literal_len(rest: &[u8]) -> Option<usize> {
+    let body = rest.strip_prefix(b"'")?;
+    let len = if body.starts_with(b"\\") {
+        // Up to the longest escape, `\u{10FFFF}`
+        2 + body.get(2..)?.iter().take(9).position(|b| *b == b'\'')?
+    } else {
+        let len = match *body.first()? {
+            b'\'' | b'\n' => return None,
+            b if b < 0x80 => 1,
+            b if b < 0xE0 => 2,
+            b if b < 0xF0 => 3,
+            _ => 4,
+        };
+        if body.get(len) != Some(&b'\'') {
+            return None;
+        }
+        len
+    };
+    Some(1 + len + 1)
+}
+
+/// Whether a raw-string prefix, like `r"`, is actually the end of an identifier
+fn is_prefixed(content: &[u8], i: usize, quote: &Quote) -> bool {
+    quote.open.as_bytes()[0].is_ascii_alphabetic()
+        && i.checked_sub(1)
+            .is_some_and(|prev| content[prev].is_ascii_alphanumeric() || content[prev] == b'_')
+}
+
+fn find(content: &[u8], start: usize, needle: &[u8]) -> Option<usize> {
+    content[start..]
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|i| start + i)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checked(syntax: &Syntax, content: &str, regions: Regions) -> String {
+        let mut checked = content.to_owned().into_bytes();
+        for span in syntax.excluded(content.as_bytes(), regions) {
+            for b in &mut checked[span] {
+                if !b.is_ascii_whitespace() {
+                    *b = b'.';
+                }
+            }
+        }
+        String::from_utf8(checked).unwrap()
+    }
+
+    const PROSE: Regions = Regions {
+        comments: true,
+        strings: true,
+        identifiers: false,
+    };
+
+    #[test]
+    fn rust_comments_and_strings() {
+        let content = r##"// Hello world
+fn foo<'a>(x: &'a str) -> &'a str {
+    let s = "a \"quoted\" string";
+    let r = r#"raw "string""#;
+    x /* inline */
+}
+"##;
+        let expected = r##"// Hello world
+.. .......... ... .... .. ... ... .
+    ... . . "a \"quoted\" string".
+    ... . . r#"raw "string""#.
+    . /* inline */
+.
+"##;
+        assert_eq!(checked(&RUST, content, PROSE), expected);
+    }
+
+    #[test]
+    fn python_triple_quotes() {
+        let content = r#"def foo():
+    """Docstring
+    continues"""
+    return 'x'  # comment
+"#;
+        let expected = r#"... ......
+    """Docstring
+    continues"""
+    ...... 'x'  # comment
+"#;
+        assert_eq!(checked(&PYTHON, content, PROSE), expected);
+    }
+
+    #[test]
+    fn only_identifiers() {
+        let content = "let x = \"string\"; // comment\n";
+        let regions = Regions {
+            comments: false,
+            strings: false,
+            identifiers: true,
+        };
+        assert_eq!(
+            checked(&C, content, regions),
+            "let x = ........; .. .......\n"
+        );
+    }
+
+    #[test]
+    fn rust_char_literals() {
+        let content =
+            "let q = '\"'; // it's\nlet e = '\\''; fn f<'a>(c: char) -> &'a str { \"é\" }\n";
+        let expected =
+            "... . . .... // it's\n... . . ..... .. ........ ..... .. ... ... . \"é\" .\n";
+        assert_eq!(checked(&RUST, content, PROSE), expected);
+    }
+
+    #[test]
+    fn trailing_char_escape() {
+        let content = "// teh\nlet x = '\\";
+        assert_eq!(checked(&RUST, content, PROSE), "// teh\n... . . ..");
+    }
+
+    #[test]
+    fn identifier_prefix_is_not_raw_string() {
+        let content = "bar\"x\"";
+        assert_eq!(checked(&RUST, content, PROSE), "...\"x\"");
+    }
+
+    #[test]
+    fn unterminated_string_stops_at_newline() {
+        let content = "x = \"open\ny = 1\n";
+        assert_eq!(checked(&C, content, PROSE), ". . \"open\n. . .\n");
+    }
+}
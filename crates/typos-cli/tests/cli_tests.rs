@@ -1,5 +1,26 @@
 #[test]
 #[cfg(feature = "dict")]
 fn cli_tests() {
-    trycmd::TestCases::new().case("tests/cmd/*.toml");
+    let cases = trycmd::TestCases::new();
+    cases.case("tests/cmd/*.toml");
+    // Expects the dictionary to be missing
+    #[cfg(feature = "wikipedia")]
+    cases.skip("tests/cmd/dictionaries-unavailable.toml");
+    match find_bin("git") {
+        Some(git) => {
+            cases.register_bin("git", git).case("tests/cmd/*.trycmd");
+        }
+        None => {
+            cases.skip("tests/cmd/*.trycmd");
+        }
+    }
+}
+
+/// Look up `name` in `PATH`, for cases that need a git repository
+#[cfg(feature = "dict")]
+fn find_bin(name: &str) -> Option<std::path::PathBuf> {
+    let name = format!("{name}{}", std::env::consts::EXE_SUFFIX);
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(&name))
+        .find(|path| path.is_file())
 }
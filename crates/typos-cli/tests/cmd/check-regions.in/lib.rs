@@ -0,0 +1,4 @@
+// Say hello
+fn hello() -> &'static str {
+    "hello world"
+}
@@ -0,0 +1,5 @@
+fn main() {
+    let attribte = 1;
+    let attribte2 = attribte;
+    println!("{attribte2}");
+}
@@ -0,0 +1 @@
+teh
@@ -0,0 +1,3 @@
+// Code generated by stringer. DO NOT EDIT.
+
+package teh
@@ -0,0 +1,2 @@
+// @generated by build.rs
+teh